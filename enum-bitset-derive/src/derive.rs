@@ -5,44 +5,103 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use syn::DeriveInput;
 
+pub use crate::derive::remote::{RemoteInput, derive_remote, remote_enum_bitset};
 use crate::derive::config::EnumBitsetConfig;
 
+mod atomic;
 mod config;
 mod core_traits;
+mod display;
 mod doc;
+mod groups;
 mod inherent;
 mod iter;
+mod kinds;
 mod mask;
+mod names;
 mod ops;
+mod ordered;
+mod remote;
 mod serde;
 mod base_impl;
+mod set_trait;
+mod subsets;
+
+pub fn derive_enum_bitset(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    if let Some(kind) = kinds::extract_kinds(&mut input)? {
+        return kinds::derive_kinds(input, kind);
+    }
 
-pub fn derive_enum_bitset(input: DeriveInput) -> syn::Result<TokenStream2> {
     let config: EnumBitsetConfig = input.try_into()?;
+    Ok(generate(config))
+}
 
+fn generate(config: EnumBitsetConfig) -> TokenStream2 {
     let inner_ty = &config.inner_type;
-    let base_ty = &config.base_type;
+    let base_ty = config.base_ty();
     let name = &config.set_type;
     let iter = &config.iter_type;
     let base_vis = &config.base_vis;
     let inner_vis = &config.inner_vis;
+    let bits = config.inner_type.bits_module(&config.my_crate);
 
     let module = format_ident!("__{}_enum_bitset", name.to_string().to_snake_case());
 
     let to_value_branches = config.base_to_value_branches();
+    let bit_to_index = config.bit_to_index_body();
+    let bit_to_base_branches = config.bit_to_base_branches();
     let core_traits = config.derive_core_traits();
     let impl_debug = config.derive_debug();
+    let impl_display = config.impl_display();
     let inherent = config.impl_inherent();
+    let groups = config.impl_groups();
+    let names = config.impl_names();
+    let ordered = config.impl_ordered();
     let impl_iter = config.impl_iter();
+    let subsets = config.impl_subsets();
+    let subset_iters = config.subset_iter_types();
+    let atomic = config.impl_atomic();
+    let atomic_cfg = config.atomic_cfg();
+    let atomic_use = config.atomic_type().map(|atomic| {
+        quote! {
+            #[doc(inline)]
+            #[allow(unused_imports)]
+            #atomic_cfg
+            #base_vis use #module::#atomic;
+        }
+    });
     let impl_serde = config.impl_serde();
     let impl_ops = config.impl_ops();
     let doc = config.set_docs();
-    let base_impl = config.base_impl();
+    let base_impl = (!config.remote).then(|| config.base_impl());
+    let set_trait = config.impl_set_trait();
+    let generic_traits = config.impl_generic_traits();
+
+    let (impl_generics, _, where_clause) = config.generics.split_for_impl();
+    let struct_generics = &config.generics.params;
+    let struct_where = &config.generics.where_clause;
+
+    // For generic enums this check is moved to the where clauses of the items that need it.
+    let clone_check = (!config.is_generic()).then(|| {
+        quote! {
+            const _: fn() = || {
+                fn base_enum_for_bitsets_must_be_clonable<T: Clone + ?Sized>() {}
+                base_enum_for_bitsets_must_be_clonable::<#base_ty>();
+            };
+        }
+    });
+
+    // `derive` would require every generic parameter to implement the derived trait, so generic
+    // sets implement them by hand instead (see `impl_generic_traits`).
+    let derives = (!config.is_generic()).then(|| {
+        quote! { #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)] }
+    });
 
-    Ok(quote! {
+    quote! {
         #[doc(inline)]
         #[allow(unused_imports)]
-        #base_vis use #module::{#name, #iter};
+        #base_vis use #module::{#name, #iter, #(#subset_iters,)*};
+        #atomic_use
 
         #[doc(hidden)]
         mod #module {
@@ -50,6 +109,7 @@ pub fn derive_enum_bitset(input: DeriveInput) -> syn::Result<TokenStream2> {
             #![allow(dead_code)]
 
             use super::*;
+            use #bits as bits;
             use ::core::{
                 borrow::Borrow,
                 clone::Clone,
@@ -62,33 +122,54 @@ pub fn derive_enum_bitset(input: DeriveInput) -> syn::Result<TokenStream2> {
                 option::Option,
             };
 
-            const _: fn() = || {
-                fn base_enum_for_bitsets_must_be_clonable<T: Clone + ?Sized>() {}
-                base_enum_for_bitsets_must_be_clonable::<#base_ty>();
-            };
+            #clone_check
 
             #doc
-            #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+            #derives
             #[repr(transparent)]
-            #inner_vis struct #name {
+            #inner_vis struct #name <#struct_generics> #struct_where {
                 /// Invariant: only the N-th last bits may be 1 (where N is the number of variants)
                 items: #inner_ty,
+                marker: PhantomData<fn() -> #base_ty>,
             }
 
             #core_traits
             #inherent
+            #groups
+            #names
+            #ordered
             #impl_debug
+            #impl_display
             #impl_iter
+            #subsets
+            #atomic
             #impl_serde
             #impl_ops
             #base_impl
+            #set_trait
+            #generic_traits
 
             #[inline]
-            const fn base_to_value(value: &#base_ty) -> #inner_ty {
+            const fn base_to_value #impl_generics (value: &#base_ty) -> #inner_ty #where_clause {
                 return match value {
                     #(#to_value_branches),*
                 }
             }
+
+            #[inline]
+            const fn bit_to_index(bit: u32) -> usize {
+                #bit_to_index
+            }
+
+            /// Creates the variant directly instead of cloning it from `VARIANTS`, so that it can
+            /// be used in `const fn`s.
+            #[inline]
+            const fn bit_to_base #impl_generics (bit: u32) -> Option<#base_ty> #where_clause {
+                match bit {
+                    #(#bit_to_base_branches,)*
+                    _ => Option::None,
+                }
+            }
         }
-    })
+    }
 }
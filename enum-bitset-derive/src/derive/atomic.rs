@@ -0,0 +1,214 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::LitStr;
+
+use crate::derive::config::{EnumBitsetConfig, Repr};
+
+impl EnumBitsetConfig {
+    /// Name of the atomic set, if `#[bitset(atomic)]` was given: `AtomicBaseSet`.
+    pub(crate) fn atomic_type(&self) -> Option<Ident> {
+        self.atomic.map(|_| format_ident!("Atomic{}", self.set_type))
+    }
+
+    /// `#[cfg(target_has_atomic = "N")]` for the width of the representation, which gates the
+    /// atomic set and its re-export: not every target has atomics of every width.
+    pub(crate) fn atomic_cfg(&self) -> TokenStream2 {
+        let Repr::Int(bits) = self.inner_type else {
+            return TokenStream2::new();
+        };
+        let width = LitStr::new(&bits.to_string(), Span::call_site());
+        quote!(#[cfg(target_has_atomic = #width)])
+    }
+
+    /// The atomic set, a wrapper around the atomic integer of the same width as the
+    /// representation. `check_atomic` already rejected the representations without one, and
+    /// targets without atomics of that width get a `compile_error!` naming it instead.
+    pub(crate) fn impl_atomic(&self) -> TokenStream2 {
+        let (Some(atomic), Some(span), Repr::Int(bits)) =
+            (self.atomic_type(), self.atomic, self.inner_type)
+        else {
+            return TokenStream2::new();
+        };
+
+        let name = &self.set_type;
+        let set_ty = self.set_ty();
+        let base = self.base_ty();
+        let base_name = self.base_name();
+        let inner_vis = &self.inner_vis;
+        let atomic_int = format_ident!("AtomicU{bits}");
+        let item_param = self.param("T");
+        let struct_generics = &self.generics.params;
+        let struct_where = &self.generics.where_clause;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let atomic_ty = quote!(#atomic #ty_generics);
+        let debug_where = self.where_clause_with(quote!(Clone + Debug));
+        let atomic_name = LitStr::new(&atomic.to_string(), Span::call_site());
+        let cfg = self.atomic_cfg();
+        let width = LitStr::new(&bits.to_string(), Span::call_site());
+        let missing = format!(
+            "`#[bitset(atomic)]` needs {bits}-bit atomics for `{atomic}`, but this target does not \
+             have `{atomic_int}`"
+        );
+
+        let doc = format!(
+            "A [`{name}`] that can be shared and modified between threads, backed by an \
+             [`{atomic_int}`](::core::sync::atomic::{atomic_int})."
+        );
+        let doc2 = format!(
+            "Every operation takes an [`Ordering`](::core::sync::atomic::Ordering), with the same \
+             meaning as for [`{atomic_int}`](::core::sync::atomic::{atomic_int})."
+        );
+        let insert_doc = format!(
+            "Inserts a variant into the set, returning `true` if it was not already in the set. \
+             Skipped variants of [`{base_name}`] are never inserted, and return `false`."
+        );
+
+        let impl_debug = self.debug.then(|| {
+            quote! {
+                #cfg
+                impl #impl_generics Debug for #atomic_ty #debug_where {
+                    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                        let set = self.load(::core::sync::atomic::Ordering::Relaxed);
+                        f.debug_tuple(#atomic_name).field(&set).finish()
+                    }
+                }
+            }
+        });
+
+        // Spanned on `atomic`, so the error points at the attribute instead of the derive.
+        let missing_error = quote_spanned! {span=>
+            #[cfg(not(target_has_atomic = #width))]
+            ::core::compile_error!(#missing);
+        };
+
+        quote! {
+            #missing_error
+
+            #cfg
+            #[doc = #doc]
+            ///
+            #[doc = #doc2]
+            #[repr(transparent)]
+            #inner_vis struct #atomic <#struct_generics> #struct_where {
+                items: ::core::sync::atomic::#atomic_int,
+                marker: PhantomData<fn() -> #base>,
+            }
+
+            #cfg
+            impl #impl_generics #atomic_ty #where_clause {
+                /// Creates a new atomic set with the variants of `set`.
+                #[inline]
+                pub const fn new(set: #set_ty) -> Self {
+                    Self { items: ::core::sync::atomic::#atomic_int::new(set.items), marker: PhantomData }
+                }
+
+                /// Consumes the atomic set, and returns the set it contains.
+                #[inline]
+                pub fn into_inner(self) -> #set_ty {
+                    #name { items: self.items.into_inner(), marker: PhantomData }
+                }
+
+                /// Returns the current value of the set.
+                #[inline]
+                pub fn load(&self, order: ::core::sync::atomic::Ordering) -> #set_ty {
+                    #name { items: self.items.load(order), marker: PhantomData }
+                }
+
+                /// Replaces the current value of the set with `set`.
+                #[inline]
+                pub fn store(&self, set: #set_ty, order: ::core::sync::atomic::Ordering) {
+                    self.items.store(set.items, order);
+                }
+
+                /// Replaces the current value of the set with `new` if it is equal to `current`.
+                /// Returns the previous value, which is `current` on success.
+                #[inline]
+                pub fn compare_exchange(
+                    &self,
+                    current: #set_ty,
+                    new: #set_ty,
+                    success: ::core::sync::atomic::Ordering,
+                    failure: ::core::sync::atomic::Ordering,
+                ) -> Result<#set_ty, #set_ty> {
+                    match self.items.compare_exchange(current.items, new.items, success, failure) {
+                        Ok(items) => Ok(#name { items, marker: PhantomData }),
+                        Err(items) => Err(#name { items, marker: PhantomData }),
+                    }
+                }
+
+                /// Inserts a variant into the set, and returns the previous value of the set.
+                #[inline]
+                pub fn fetch_insert<#item_param: Borrow<#base>>(
+                    &self,
+                    item: #item_param,
+                    order: ::core::sync::atomic::Ordering,
+                ) -> #set_ty {
+                    let items = self.items.fetch_or(base_to_value(item.borrow()), order);
+                    #name { items, marker: PhantomData }
+                }
+
+                /// Removes a variant from the set, and returns the previous value of the set.
+                #[inline]
+                pub fn fetch_remove<#item_param: Borrow<#base>>(
+                    &self,
+                    item: #item_param,
+                    order: ::core::sync::atomic::Ordering,
+                ) -> #set_ty {
+                    let items = self.items.fetch_and(!base_to_value(item.borrow()), order);
+                    #name { items, marker: PhantomData }
+                }
+
+                /// Adds all the variants of `other` to the set, and returns the previous value of
+                /// the set.
+                #[inline]
+                pub fn fetch_union(&self, other: #set_ty, order: ::core::sync::atomic::Ordering) -> #set_ty {
+                    let items = self.items.fetch_or(other.items, order);
+                    #name { items, marker: PhantomData }
+                }
+
+                #[doc = #insert_doc]
+                #[inline]
+                pub fn insert<#item_param: Borrow<#base>>(
+                    &self,
+                    item: #item_param,
+                    order: ::core::sync::atomic::Ordering,
+                ) -> bool {
+                    let value = base_to_value(item.borrow());
+                    let previous = self.items.fetch_or(value, order);
+                    !bits::is_empty(bits::and_not(value, previous))
+                }
+
+                /// Removes a variant from the set, returning `true` if it was in the set.
+                #[inline]
+                pub fn remove<#item_param: Borrow<#base>>(
+                    &self,
+                    item: #item_param,
+                    order: ::core::sync::atomic::Ordering,
+                ) -> bool {
+                    let value = base_to_value(item.borrow());
+                    let previous = self.items.fetch_and(!value, order);
+                    !bits::is_empty(bits::and(value, previous))
+                }
+            }
+
+            #cfg
+            impl #impl_generics Default for #atomic_ty #where_clause {
+                /// Creates an empty atomic set.
+                #[inline]
+                fn default() -> Self {
+                    Self::new(<#set_ty>::empty())
+                }
+            }
+
+            #cfg
+            impl #impl_generics From<#set_ty> for #atomic_ty #where_clause {
+                #[inline]
+                fn from(set: #set_ty) -> Self {
+                    Self::new(set)
+                }
+            }
+
+            #impl_debug
+        }
+    }
+}
@@ -5,18 +5,146 @@ use crate::derive::config::EnumBitsetConfig;
 
 impl EnumBitsetConfig {
     pub fn base_impl(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base_type = self.base_name();
         let set_type = &self.set_type;
+        let base_ty = self.base_ty();
+        let set_ty = self.set_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
         let doc = format!(
-            r#"Creates a new [`{set_type}`] from a [`{base_ty}`] value. Can be used in
+            r#"Creates a new [`{set_type}`] from a [`{base_type}`] value. Can be used in
                const contexts."#
         );
 
+        let base_trait = self.impl_base_trait();
+        let index = self.base_index.then(|| self.impl_index());
+
         quote! {
             #[doc = #doc]
-            impl #base_ty {
-                pub const fn as_bitset(&self) -> #set_type {
-                    #set_type { items: base_to_value(self) }
+            impl #impl_generics #base_ty #where_clause {
+                pub const fn as_bitset(&self) -> #set_ty {
+                    #set_type { items: base_to_value(self), marker: PhantomData }
+                }
+
+                #index
+            }
+
+            #base_trait
+        }
+    }
+
+    /// `index` and `from_index`, to map the variants to dense indices and back. Left out with
+    /// `#[bitset(no_base_index)]`, for enums that already have methods with those names.
+    fn impl_index(&self) -> TokenStream2 {
+        let base_name = self.base_name();
+        let [index_body, from_index_body] = self.index_bodies();
+
+        let index_doc = format!(
+            "The position of the variant in the declaration of [`{base_name}`], starting at 0. \
+             Skipped variants are not counted, so the indices are dense."
+        );
+        let panics_doc = format!("Panics if called on a skipped variant of [`{base_name}`].");
+        let from_index_doc = format!(
+            "Returns the variant of [`{base_name}`] with the given [`index`](Self::index), or \
+             `None` if it is out of range."
+        );
+
+        quote! {
+            #[doc = #index_doc]
+            ///
+            /// # Panics
+            ///
+            #[doc = #panics_doc]
+            #[inline]
+            pub const fn index(&self) -> usize {
+                #index_body
+            }
+
+            #[doc = #from_index_doc]
+            #[inline]
+            pub const fn from_index(index: usize) -> Option<Self> {
+                #from_index_body
+            }
+        }
+    }
+
+    /// Bodies of `index` and `from_index`, shared by the inherent methods and the `BitsetBase`
+    /// implementation.
+    fn index_bodies(&self) -> [TokenStream2; 2] {
+        let base_ty = &self.base_type;
+        let base_name = self.base_name();
+
+        let index_branches = self
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let name = &variant.ident;
+                quote! {#base_ty::#name => #index}
+            });
+
+        let skipped_branches = self.skipped.iter().map(|variant| {
+            let name = &variant.ident;
+            let msg = format!("`{base_name}::{name}` is skipped, and has no index");
+            quote! {#base_ty::#name { .. } => ::core::panic!(#msg)}
+        });
+
+        let from_index_branches = self
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let name = &variant.ident;
+                quote! {#index => Option::Some(#base_ty::#name)}
+            });
+
+        [
+            quote! {
+                match self {
+                    #(#index_branches,)*
+                    #(#skipped_branches,)*
+                }
+            },
+            quote! {
+                match index {
+                    #(#from_index_branches,)*
+                    _ => Option::None,
+                }
+            },
+        ]
+    }
+
+    fn impl_base_trait(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let base = self.base_ty();
+        let my_crate = &self.my_crate;
+        let len = self.len();
+        let (impl_generics, _, _) = self.generics.split_for_impl();
+        let where_clause = self.where_clause_with(quote!(Clone + 'static));
+        let value = self.param("V");
+        let init = self.param("F");
+        let [index_body, from_index_body] = self.index_bodies();
+
+        quote! {
+            impl #impl_generics #my_crate::BitsetBase for #base #where_clause {
+                type Set = #set_ty;
+
+                const COUNT: usize = #len;
+
+                #[inline]
+                fn index(&self) -> usize {
+                    #index_body
+                }
+
+                #[inline]
+                fn from_index(index: usize) -> Option<Self> {
+                    #from_index_body
+                }
+
+                type Array<#value> = [#value; #len];
+
+                #[inline]
+                fn array_from_fn<#value, #init: FnMut(usize) -> #value>(f: #init) -> [#value; #len] {
+                    ::core::array::from_fn(f)
                 }
             }
         }
@@ -3,36 +3,77 @@ use std::convert::TryFrom;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
 use syn::{
-    Attribute, Data, DeriveInput, Error, Fields, Lit, Path, Result, Variant, Visibility,
+    Attribute, Data, DeriveInput, Error, Generics, Lit, LitStr, Path, Result, Type, Visibility,
     meta::ParseNestedMeta, parse_str, spanned::Spanned,
 };
 
-use crate::derive::serde::SerdeConfig;
+pub use crate::derive::config::{
+    rename::RenameRule,
+    repr::Repr,
+    variant::{BitLayout, BitsetVariant, SkippedVariant},
+};
+use crate::derive::{
+    display::DisplayConfig,
+    serde::{DuplicatePolicy, SerdeConfig, SerdeFormat, UnknownPolicy},
+};
 
+mod generics;
+mod rename;
+mod repr;
+mod variant;
 mod vis;
 
 pub struct EnumBitsetConfig {
-    pub base_type: Ident,
+    /// Path to the base enum. Only a plain identifier, unless the set is declared with `remote!`.
+    pub base_type: Path,
+    /// Generic parameters of the base enum, shared by the set and its iterator.
+    pub generics: Generics,
     pub set_type: Ident,
-    pub inner_type: Ident,
+    pub inner_type: Repr,
+    /// Set when `repr` is given explicitly, to check it once the variants are known.
+    pub repr_span: Option<Span>,
     pub iter_type: Ident,
     pub debug: bool,
-    pub variants: Vec<Variant>,
+    /// Set with `#[bitset(display)]`, to implement `Display` and `FromStr`.
+    pub display: Option<DisplayConfig>,
+    /// Set with `#[bitset(atomic)]`, to generate an atomic version of the set.
+    pub atomic: Option<Span>,
+    pub variants: Vec<BitsetVariant>,
+    /// Variants excluded from the set with `#[bitset(skip)]`.
+    pub skipped: Vec<SkippedVariant>,
+    pub bit_layout: BitLayout,
+    /// Case conversion of the names of the variants, from `#[bitset(rename_all = "..")]`.
+    pub rename_all: Option<RenameRule>,
     pub base_vis: Visibility,
     pub inner_vis: Visibility,
     pub base_add: bool,
+    /// Cleared with `#[bitset(no_base_index)]`, to leave out the inherent `index` and
+    /// `from_index` of the base enum.
+    pub base_index: bool,
+    /// The base enum is declared elsewhere (see `remote!`), so nothing is implemented on it.
+    pub remote: bool,
+    /// The base enum is the enum of kinds of this enum with data (see `#[bitset(kinds)]`), so
+    /// `contains` also accepts references to its values.
+    pub kinds_of: Option<Ident>,
     pub my_crate: Path,
     pub serde: SerdeConfig,
 }
 
-static INVALID_SERDE_MSG: &str = "Invalid value for serde. Valid values are: `true`, `false`, \"de\", \"ser\", \"both\" (same as `true`), and \"none\" (same as `false`).";
-static INVALID_ATTR_MSG: &str =
-    "Invalid attribute value. Valid values are: `serde`, `serde_crate`, `no_debug`, and `crate`.";
+static INVALID_SERDE_MSG: &str = "Invalid value for serde. Valid values are: `true`, `false`, \"de\", \"ser\", \"both\" (same as `true`), \"none\" (same as `false`), \"repr\", \"map\", \"string\", and \"adaptive\".";
+static INVALID_SERDE_UNKNOWN_MSG: &str =
+    "Invalid value for serde_unknown. Valid values are: \"error\" (the default), and \"ignore\".";
+static INVALID_SERDE_DUPLICATES_MSG: &str =
+    "Invalid value for serde_duplicates. Valid values are: \"ignore\" (the default), and \"error\".";
+static INVALID_BITS_MSG: &str =
+    "Invalid value for bits. Valid values are: \"declaration\" (the default), and \"discriminant\".";
+static INVALID_ATTR_MSG: &str = "Invalid attribute value. Valid values are: `name`, `repr`, `bits`, `no_debug`, `no_base_ops`, `no_base_index`, `display`, `atomic`, `kinds`, `rename_all`, `serde`, `serde_crate`, `serde_unknown`, `serde_duplicates`, `sep`, and `crate`.";
 static ONLY_ENUM_MSG: &str = "EnumBitset can only be derived for enums";
 static NO_VARIANTS: &str = "EnumBitset cannot be derived for enums with no variants";
-static ALL_UNIT_MSG: &str = "EnumBitset can only be derived for enums with all unit variants.";
-static TOO_MANY_VARIANTS_MSG: &str = "Too many variants! At most 128 are supported.";
-const INVALID_REPR_MSG: &str = "Invalid bitset representation: must be a primitive unsigned integer (u8, u16, u32, u64, u128).";
+static ALL_SKIPPED: &str = "EnumBitset cannot be derived for enums where all the variants are skipped";
+/// Highest number of bits of a set whose `repr` is chosen by the macro (16 `u64` words), so that
+/// a typo in a bit position does not produce a huge set.
+const MAX_IMPLICIT_BITS: usize = 1024;
+const INVALID_REPR_MSG: &str = "Invalid bitset representation: must be a primitive unsigned integer (u8, u16, u32, u64, u128) or an array of u64 words (`[u64; N]`).";
 
 
 
@@ -53,18 +94,39 @@ impl TryFrom<DeriveInput> for EnumBitsetConfig {
         let mut config = Self {
             iter_type: format_ident!("{set_type}SetIter"),
             set_type,
-            inner_type: Self::inner_type(data.variants.len())?,
-            variants: Self::parse_variants(data.variants)?,
+            inner_type: Repr::Int(8),
+            repr_span: None,
+            variants: Vec::new(),
+            skipped: Vec::new(),
+            bit_layout: BitLayout::Declaration,
+            rename_all: None,
             inner_vis: vis::compute_visibility(&input.vis),
             base_vis: input.vis,
-            base_type: input.ident,
+            base_type: input.ident.into(),
+            generics: input.generics,
             my_crate: parse_str("::enum_bitset")?,
             serde: SerdeConfig::default(),
             debug: true,
+            display: None,
+            atomic: None,
             base_add: true,
+            base_index: true,
+            remote: false,
+            kinds_of: None,
         };
 
         config.parse_attrs(input.attrs)?;
+        config.serde.check()?;
+        let variants_span = data.variants.span();
+        (config.variants, config.skipped) =
+            variant::parse_variants(data.variants, config.bit_layout)?;
+        if config.variants.is_empty() {
+            return Err(Error::new(variants_span, ALL_SKIPPED));
+        }
+        config.check_repr()?;
+        config.check_atomic()?;
+        config.check_names()?;
+        config.check_groups()?;
 
         Ok(config)
     }
@@ -76,26 +138,87 @@ impl EnumBitsetConfig {
         self.variants.len()
     }
 
-    pub(crate) fn base_to_value_branches(&self) -> impl Iterator<Item = TokenStream2> + Clone + '_ {
+    /// The name of a variant, used by `NAMES`, `Display` and `FromStr`: its explicit `rename`, or
+    /// its identifier after applying `rename_all`.
+    pub(crate) fn name_of(&self, ident: &Ident, rename: Option<&String>) -> String {
+        match (rename, self.rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply(&ident.to_string()),
+            (None, None) => ident.to_string(),
+        }
+    }
+
+    /// Number of bits needed to represent the set: one past the highest bit position.
+    fn width_of(variants: &[BitsetVariant]) -> usize {
+        variants
+            .iter()
+            .map(|variant| variant.bit + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether the N-th variant is represented by the N-th bit, for every variant.
+    pub(crate) fn is_dense(&self) -> bool {
         self.variants
             .iter()
             .enumerate()
-            .map(move |(index, variant)| {
-                let name = &variant.ident;
-                let base = &self.base_type;
+            .all(|(index, variant)| variant.bit == index)
+    }
 
-                quote! {#base::#name => const { 1 << #index }}
-            })
+    pub(crate) fn base_to_value_branches(&self) -> impl Iterator<Item = TokenStream2> + Clone + '_ {
+        let base = &self.base_type;
+
+        let variants = self.variants.iter().map(move |variant| {
+            let name = &variant.ident;
+
+            let bit = variant.bit as u32;
+
+            quote! {#base::#name => const { bits::bit(#bit) }}
+        });
+
+        let skipped = self.skipped.iter().map(move |variant| {
+            let name = &variant.ident;
+
+            quote! {#base::#name { .. } => bits::empty()}
+        });
+
+        variants.chain(skipped)
     }
 
-    fn parse_variants(variants: impl IntoIterator<Item = Variant>) -> Result<Vec<Variant>> {
-        variants
-            .into_iter()
-            .map(|variant| match variant.fields {
-                Fields::Unit => Ok(variant),
-                _ => Err(Error::new(variant.span(), ALL_UNIT_MSG)),
-            })
-            .collect()
+    /// Branches of the `bit_to_base` function, that maps a bit position back to its variant.
+    pub(crate) fn bit_to_base_branches(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let base = &self.base_type;
+
+        self.variants.iter().map(move |variant| {
+            let name = &variant.ident;
+            let bit = variant.bit as u32;
+
+            quote! {#bit => Option::Some(#base::#name)}
+        })
+    }
+
+    /// Body of the `bit_to_index` function, that maps a bit position to the index of the
+    /// corresponding variant in `VARIANTS`.
+    pub(crate) fn bit_to_index_body(&self) -> TokenStream2 {
+        if self.is_dense() {
+            return quote! { bit as usize };
+        }
+
+        let branches = self
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let bit = variant.bit as u32;
+                quote! {#bit => #index}
+            });
+
+        quote! {
+            match bit {
+                #(#branches,)*
+                _ => unreachable!(),
+            }
+        }
     }
 
     fn parse_attrs(&mut self, attrs: Vec<Attribute>) -> Result<()> {
@@ -118,6 +241,32 @@ impl EnumBitsetConfig {
             return self.parse_serde_attr(&meta);
         };
 
+        if meta.path.is_ident("serde_unknown") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.serde.unknown = match value.value().as_str() {
+                "error" => UnknownPolicy::Error,
+                "ignore" => UnknownPolicy::Ignore,
+                _ => return Err(Error::new(value.span(), INVALID_SERDE_UNKNOWN_MSG)),
+            };
+            self.serde.unknown_span = Some(value.span());
+            return Ok(());
+        }
+
+        if meta.path.is_ident("serde_duplicates") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.serde.duplicates = match value.value().as_str() {
+                "ignore" => DuplicatePolicy::Ignore,
+                "error" => DuplicatePolicy::Error,
+                _ => return Err(Error::new(value.span(), INVALID_SERDE_DUPLICATES_MSG)),
+            };
+            return Ok(());
+        }
+
+        if meta.path.is_ident("sep") {
+            self.serde.sep = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
         if meta.path.is_ident("serde_crate") {
             self.serde.serde_crate = meta.value()?.parse()?;
             return Ok(());
@@ -128,6 +277,16 @@ impl EnumBitsetConfig {
             return Ok(());
         }
 
+        if meta.path.is_ident("display") {
+            self.display = Some(DisplayConfig::parse(&meta)?);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("atomic") {
+            self.atomic = Some(meta.path.span());
+            return Ok(());
+        }
+
         if meta.path.is_ident("crate") {
             self.my_crate = meta.value()?.parse()?;
             return Ok(());
@@ -135,6 +294,28 @@ impl EnumBitsetConfig {
 
         if meta.path.is_ident("repr") {
             self.inner_type = self.parse_repr_attr(&meta)?;
+            self.repr_span = Some(meta.path.span());
+            return Ok(());
+        }
+
+        if meta.path.is_ident("bits") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.bit_layout = match value.value().as_str() {
+                "declaration" => BitLayout::Declaration,
+                "discriminant" => BitLayout::Discriminant,
+                _ => return Err(Error::new(value.span(), INVALID_BITS_MSG)),
+            };
+            return Ok(());
+        }
+
+        if meta.path.is_ident("rename_all") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.rename_all = Some(RenameRule::parse(&value.value()).ok_or_else(|| {
+                Error::new(value.span(), format!(
+                    "Invalid value for rename_all. Valid values are: {}.",
+                    RenameRule::VALID_RULES
+                ))
+            })?);
             return Ok(());
         }
 
@@ -143,6 +324,11 @@ impl EnumBitsetConfig {
             return Ok(());
         }
 
+        if meta.path.is_ident("no_base_index") {
+            self.base_index = false;
+            return Ok(());
+        }
+
         Err(Error::new(meta.input.span(), INVALID_ATTR_MSG))
     }
 
@@ -180,57 +366,159 @@ impl EnumBitsetConfig {
                     self.serde.ser = false;
                     Ok(())
                 }
+                "repr" => {
+                    self.serde.de = true;
+                    self.serde.ser = true;
+                    self.serde.format = SerdeFormat::Repr;
+                    Ok(())
+                }
+                "map" => {
+                    self.serde.de = true;
+                    self.serde.ser = true;
+                    self.serde.format = SerdeFormat::Map;
+                    Ok(())
+                }
+                "string" => {
+                    self.serde.de = true;
+                    self.serde.ser = true;
+                    self.serde.format = SerdeFormat::String;
+                    Ok(())
+                }
+                "adaptive" => {
+                    self.serde.de = true;
+                    self.serde.ser = true;
+                    self.serde.format = SerdeFormat::Adaptive;
+                    Ok(())
+                }
                 _ => Err(Error::new(meta.input.span(), INVALID_SERDE_MSG)),
             },
             _ => Err(Error::new(meta.input.span(), INVALID_SERDE_MSG)),
         }
     }
 
-    fn inner_type(n_variants: usize) -> Result<Ident> {
-        let len = if n_variants <= 8 {
-            8
-        } else if n_variants <= 16 {
-            16
-        } else if n_variants <= 32 {
-            32
-        } else if n_variants <= 64 {
-            64
-        } else if n_variants <= 128 {
-            128
-        } else {
-            return Err(Error::new(Span::call_site(), TOO_MANY_VARIANTS_MSG));
-        };
-
-        Ok(format_ident!("u{len}"))
-    }
-
-    fn parse_repr_attr(&mut self, meta: &ParseNestedMeta) -> Result<Ident> {
-        let ty: Path = meta
+    fn parse_repr_attr(&mut self, meta: &ParseNestedMeta) -> Result<Repr> {
+        let ty: Type = meta
             .value()?
             .parse()
             .map_err(|_| meta.error(INVALID_REPR_MSG))?;
-        let ty = ty.require_ident()?.to_string();
 
-        if !ty.starts_with("u") {
-            return Err(meta.error(INVALID_REPR_MSG));
+        Repr::parse(&ty).ok_or_else(|| meta.error(INVALID_REPR_MSG))
+    }
+
+    /// Checks that no two variants of the set end up with the same name, and that the names can be
+    /// told apart from the separator of the string formats.
+    fn check_names(&self) -> Result<()> {
+        for (index, variant) in self.variants.iter().enumerate() {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+
+            if let Some(other) = self.variants[..index]
+                .iter()
+                .find(|other| self.name_of(&other.ident, other.rename.as_ref()) == name)
+            {
+                return Err(Error::new(variant.ident.span(), format!(
+                    "Duplicate name \"{name}\": both `{}` and `{}` are named like that.",
+                    other.ident, variant.ident
+                )));
+            }
         }
 
-        let n: usize = ty[1..]
-            .parse()
-            .map_err(|_| meta.error(INVALID_REPR_MSG))?;
+        if self.serde.format == SerdeFormat::String {
+            self.check_sep(&self.serde.sep(), "sep")?;
+        }
+        if let Some(display) = &self.display {
+            self.check_sep(&display.sep, "display(sep = ..)")?;
+        }
+
+        Ok(())
+    }
 
-        if ![8, 16, 32, 64, 128].contains(&n) {
-            return Err(meta.error(INVALID_REPR_MSG));
+    /// Checks that no name contains `sep`, the separator of the names in a string, since it would
+    /// split the name in two when parsing the string back. As when parsing, whitespace around the
+    /// separator is ignored, and a blank separator splits the string by whitespace.
+    fn check_sep(&self, sep: &str, option: &str) -> Result<()> {
+        let sep = sep.trim();
+
+        for variant in &self.variants {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+            let (clash, shown) = match sep {
+                "" => (name.contains(char::is_whitespace), "whitespace".into()),
+                sep => (name.contains(sep), format!("{sep:?}")),
+            };
+
+            if clash {
+                return Err(Error::new(variant.ident.span(), format!(
+                    "The name \"{name}\" of `{}` contains the separator given by `{option}` ({shown}), so it could not be parsed back.",
+                    variant.ident
+                )));
+            }
         }
 
-        if n < self.variants.len() {
-            return Err(meta.error(format!(
-                "Invalid bitset representation: {} has {} variants, but the requested bitset representation is only {n} bits wide.",
-                self.base_type,
+        Ok(())
+    }
+
+    /// Chooses the representation of the set, or checks that the one requested explicitly is
+    /// wide enough for all the variants.
+    fn check_repr(&mut self) -> Result<()> {
+        let Some(span) = self.repr_span else {
+            if let Some(variant) = self
+                .variants
+                .iter()
+                .find(|variant| variant.bit >= MAX_IMPLICIT_BITS)
+            {
+                return Err(Error::new(variant.ident.span(), format!(
+                    "Bit position out of range: {}::{} is assigned to bit {}, but bit positions must be below {MAX_IMPLICIT_BITS} unless a `repr` is given, like `#[bitset(repr = [u64; N])]`.",
+                    self.base_name(),
+                    variant.ident,
+                    variant.bit,
+                )));
+            }
+
+            self.inner_type = Repr::for_bits(Self::width_of(&self.variants));
+            return Ok(());
+        };
+
+        let repr = self.inner_type;
+
+        if repr.bits() < self.variants.len() {
+            return Err(Error::new(span, format!(
+                "Invalid bitset representation: {} has {} variants, but the requested bitset representation is only {} bits wide.",
+                self.base_name(),
                 self.variants.len(),
+                repr.bits(),
+            )));
+        }
+
+        if let Some(variant) = self
+            .variants
+            .iter()
+            .find(|variant| variant.bit >= repr.bits())
+        {
+            return Err(Error::new(span, format!(
+                "Invalid bitset representation: {}::{} is assigned to bit {}, but the requested bitset representation is only {} bits wide.",
+                self.base_name(),
+                variant.ident,
+                variant.bit,
+                repr.bits(),
             )));
         }
 
-        Ok(format_ident!("u{n}"))
+        Ok(())
+    }
+
+    /// The atomic set needs an atomic integer of the same width as the representation, which
+    /// only exist up to 64 bits.
+    fn check_atomic(&self) -> Result<()> {
+        let Some(span) = self.atomic else {
+            return Ok(());
+        };
+
+        match self.inner_type {
+            Repr::Int(bits) if bits <= 64 => Ok(()),
+            repr => Err(Error::new(span, format!(
+                "`atomic` requires a bitset representation of at most 64 bits, but {} uses {}. There is no stable `AtomicU128`, nor an atomic array of words.",
+                self.base_name(),
+                repr,
+            ))),
+        }
     }
 }
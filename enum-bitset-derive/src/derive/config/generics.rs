@@ -0,0 +1,142 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{GenericParam, Generics, Lifetime, LifetimeParam, WhereClause, parse_quote};
+
+use crate::derive::config::EnumBitsetConfig;
+
+impl EnumBitsetConfig {
+    /// Whether the base enum has generic parameters (types, lifetimes or constants).
+    pub(crate) fn is_generic(&self) -> bool {
+        !self.generics.params.is_empty()
+    }
+
+    /// The base enum as a type, with its generic parameters: `Base<'a, T>`.
+    pub(crate) fn base_ty(&self) -> TokenStream2 {
+        let base = &self.base_type;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#base #ty_generics)
+    }
+
+    /// The path to the base enum as written in the docs and error messages: `Base`, or
+    /// `module::Base` for remote enums.
+    pub(crate) fn base_name(&self) -> String {
+        let base = &self.base_type;
+        quote!(#base).to_string().replace(' ', "")
+    }
+
+    /// The set as a type, with the generic parameters of the base enum.
+    pub(crate) fn set_ty(&self) -> TokenStream2 {
+        let name = &self.set_type;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#name #ty_generics)
+    }
+
+    /// The iterator of the set as a type, with the generic parameters of the base enum.
+    pub(crate) fn iter_ty(&self) -> TokenStream2 {
+        let iter = &self.iter_type;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#iter #ty_generics)
+    }
+
+    /// Name for a generic parameter of a generated item, which does not clash with the ones of
+    /// the base enum. That is, `name`, unless the base enum already uses it.
+    pub(crate) fn param(&self, name: &str) -> Ident {
+        let taken = self.generics.params.iter().any(|param| match param {
+            GenericParam::Type(param) => param.ident == name,
+            GenericParam::Const(param) => param.ident == name,
+            GenericParam::Lifetime(_) => false,
+        });
+
+        if taken {
+            format_ident!("__{name}")
+        } else {
+            format_ident!("{name}")
+        }
+    }
+
+    /// Same as [`param`](Self::param), but for lifetimes. `name` does not include the `'`.
+    pub(crate) fn lifetime(&self, name: &str) -> Lifetime {
+        let taken = self
+            .generics
+            .lifetimes()
+            .any(|param| param.lifetime.ident == name);
+
+        let name = if taken {
+            format!("'__{name}")
+        } else {
+            format!("'{name}")
+        };
+        Lifetime::new(&name, Span::call_site())
+    }
+
+    /// The generics of the base enum, with an additional lifetime parameter.
+    pub(crate) fn generics_with_lifetime(&self, lifetime: &Lifetime) -> Generics {
+        let mut generics = self.generics.clone();
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+        generics
+    }
+
+    /// The where clause of the base enum, with the additional `bounds` on the base enum type.
+    /// `bounds` are only added when the enum is generic, since they are always satisfied
+    /// otherwise (or reported where the bounds are required).
+    pub(crate) fn where_clause_with(&self, bounds: TokenStream2) -> Option<WhereClause> {
+        let mut where_clause = self.generics.where_clause.clone();
+
+        if self.is_generic() {
+            let base_ty = self.base_ty();
+            where_clause
+                .get_or_insert_with(|| parse_quote!(where))
+                .predicates
+                .push(parse_quote!(#base_ty: #bounds));
+        }
+
+        where_clause
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use syn::{DeriveInput, parse_str};
+
+    use super::*;
+
+    fn parse(input: &str) -> EnumBitsetConfig {
+        let input: DeriveInput = parse_str(input).unwrap();
+        input.try_into().unwrap()
+    }
+
+    #[test]
+    fn types() {
+        let config = parse("enum Kind<'a, T: Clone> { A, #[bitset(skip)] B(&'a T) }");
+        assert_eq!(config.base_ty().to_string(), "Kind < 'a , T >");
+        assert_eq!(config.set_ty().to_string(), "KindSet < 'a , T >");
+
+        let config = parse("enum Kind { A }");
+        assert_eq!(config.base_ty().to_string(), "Kind");
+    }
+
+    #[test]
+    fn params_do_not_clash() {
+        let config = parse("enum Kind<'a, T> { A, #[bitset(skip)] B(&'a T) }");
+        assert_eq!(config.param("T"), "__T");
+        assert_eq!(config.param("I"), "I");
+        assert_eq!(config.lifetime("a").to_string(), "'__a");
+        assert_eq!(config.lifetime("de").to_string(), "'de");
+    }
+
+    #[test]
+    fn where_clause() {
+        let config = parse("enum Kind<T> where T: Copy { A, #[bitset(skip)] B(T) }");
+        let where_clause = config.where_clause_with(quote!(Clone));
+        assert_eq!(
+            quote!(#where_clause).to_string(),
+            "where T : Copy , Kind < T > : Clone"
+        );
+
+        let config = parse("enum Kind { A }");
+        assert!(config.where_clause_with(quote!(Clone)).is_none());
+    }
+}
@@ -0,0 +1,74 @@
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+    ToUpperCamelCase,
+};
+
+/// The case conversion applied to the names of the variants, with `#[bitset(rename_all = "..")]`.
+/// The names of the rules are the same ones used by serde.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub const VALID_RULES: &'static str = "\"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\", and \"SCREAMING-KEBAB-CASE\"";
+
+    pub fn parse(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => name.to_upper_camel_case(),
+            RenameRule::Camel => name.to_lower_camel_case(),
+            RenameRule::Snake => name.to_snake_case(),
+            RenameRule::ScreamingSnake => name.to_shouty_snake_case(),
+            RenameRule::Kebab => name.to_kebab_case(),
+            RenameRule::ScreamingKebab => name.to_shouty_kebab_case(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules() {
+        let rename = |rule: &str| {
+            RenameRule::parse(rule)
+                .unwrap()
+                .apply("WorksOnMyMachine")
+        };
+
+        assert_eq!(rename("lowercase"), "worksonmymachine");
+        assert_eq!(rename("UPPERCASE"), "WORKSONMYMACHINE");
+        assert_eq!(rename("PascalCase"), "WorksOnMyMachine");
+        assert_eq!(rename("camelCase"), "worksOnMyMachine");
+        assert_eq!(rename("snake_case"), "works_on_my_machine");
+        assert_eq!(rename("SCREAMING_SNAKE_CASE"), "WORKS_ON_MY_MACHINE");
+        assert_eq!(rename("kebab-case"), "works-on-my-machine");
+        assert_eq!(rename("SCREAMING-KEBAB-CASE"), "WORKS-ON-MY-MACHINE");
+        assert!(RenameRule::parse("Title Case").is_none());
+    }
+}
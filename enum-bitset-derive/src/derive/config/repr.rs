@@ -0,0 +1,151 @@
+use std::fmt::{self, Display, Formatter};
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Expr, ExprLit, Lit, Path, Type};
+
+/// The type used to store the bits of a set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Repr {
+    /// A single primitive unsigned integer, `u{bits}`.
+    Int(usize),
+    /// An array of `u64` words, `[u64; {words}]`.
+    Words(usize),
+}
+
+impl Repr {
+    pub const WORD_BITS: usize = 64;
+
+    /// The smallest representation able to hold `n_bits` bits.
+    pub fn for_bits(n_bits: usize) -> Self {
+        match n_bits {
+            0..=8 => Repr::Int(8),
+            9..=16 => Repr::Int(16),
+            17..=32 => Repr::Int(32),
+            33..=64 => Repr::Int(64),
+            65..=128 => Repr::Int(128),
+            _ => Repr::Words(n_bits.div_ceil(Self::WORD_BITS)),
+        }
+    }
+
+    /// Parses the value of a `repr` attribute: either `uN` or `[u64; N]`.
+    pub fn parse(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Path(path) if path.qself.is_none() => Self::parse_int(&path.path),
+            Type::Array(array) => {
+                let Type::Path(elem) = &*array.elem else {
+                    return None;
+                };
+
+                if Self::parse_int(&elem.path) != Some(Repr::Int(Self::WORD_BITS)) {
+                    return None;
+                }
+
+                match &array.len {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(len), ..
+                    }) => match len.base10_parse() {
+                        Ok(0) | Err(_) => None,
+                        Ok(len) => Some(Repr::Words(len)),
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_int(path: &Path) -> Option<Self> {
+        let ty = path.get_ident()?.to_string();
+        let n: usize = ty.strip_prefix("u")?.parse().ok()?;
+
+        [8, 16, 32, 64, 128]
+            .contains(&n)
+            .then_some(Repr::Int(n))
+    }
+
+    /// The number of bits that this representation can hold.
+    pub fn bits(&self) -> usize {
+        match self {
+            Repr::Int(bits) => *bits,
+            Repr::Words(words) => words * Self::WORD_BITS,
+        }
+    }
+
+    /// Path to the module of the runtime crate that implements the bit operations for this
+    /// representation.
+    pub fn bits_module(&self, my_crate: &Path) -> TokenStream2 {
+        match self {
+            Repr::Int(bits) => {
+                let module = format_ident!("u{bits}");
+                quote!(#my_crate::bits::#module)
+            }
+            Repr::Words(_) => quote!(#my_crate::bits::words),
+        }
+    }
+}
+
+impl ToTokens for Repr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Repr::Int(bits) => format_ident!("u{bits}").to_tokens(tokens),
+            Repr::Words(words) => quote!([u64; #words]).to_tokens(tokens),
+        }
+    }
+}
+
+impl Display for Repr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Repr::Int(bits) => write!(f, "u{bits}"),
+            Repr::Words(words) => write!(f, "[u64; {words}]"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use super::*;
+
+    fn parse(ty: &str) -> Option<Repr> {
+        Repr::parse(&parse_str(ty).unwrap())
+    }
+
+    #[test]
+    fn for_bits() {
+        assert_eq!(Repr::for_bits(1), Repr::Int(8));
+        assert_eq!(Repr::for_bits(9), Repr::Int(16));
+        assert_eq!(Repr::for_bits(64), Repr::Int(64));
+        assert_eq!(Repr::for_bits(128), Repr::Int(128));
+        assert_eq!(Repr::for_bits(129), Repr::Words(3));
+        assert_eq!(Repr::for_bits(192), Repr::Words(3));
+        assert_eq!(Repr::for_bits(193), Repr::Words(4));
+    }
+
+    #[test]
+    fn parse_int() {
+        assert_eq!(parse("u8"), Some(Repr::Int(8)));
+        assert_eq!(parse("u128"), Some(Repr::Int(128)));
+        assert_eq!(parse("u7"), None);
+        assert_eq!(parse("i32"), None);
+        assert_eq!(parse("usize"), None);
+        assert_eq!(parse("core::u8"), None);
+    }
+
+    #[test]
+    fn parse_words() {
+        assert_eq!(parse("[u64; 4]"), Some(Repr::Words(4)));
+        assert_eq!(parse("[u64; 0]"), None);
+        assert_eq!(parse("[u32; 4]"), None);
+        assert_eq!(parse("[u64; N]"), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Repr::Int(16).to_string(), "u16");
+        assert_eq!(Repr::Words(3).to_string(), "[u64; 3]");
+    }
+}
@@ -0,0 +1,294 @@
+use proc_macro2::Ident;
+use syn::{
+    Error, Expr, ExprLit, Fields, Lit, LitInt, LitStr, Result, Variant, meta::ParseNestedMeta,
+    spanned::Spanned,
+};
+
+static ALL_UNIT_MSG: &str = "EnumBitset can only be derived for enums with all unit variants. Variants with fields must be excluded from the set with `#[bitset(skip)]`.";
+static INVALID_VARIANT_ATTR_MSG: &str =
+    "Invalid variant attribute value. Valid values are: `bit`, `skip`, `group`, and `rename`.";
+static BIT_WITH_SKIP_MSG: &str = "`#[bitset(bit = N)]` cannot be used on a skipped variant.";
+static GROUP_WITH_SKIP_MSG: &str = "`#[bitset(group(..))]` cannot be used on a skipped variant.";
+static INVALID_DISCRIMINANT_MSG: &str = "With `#[bitset(bits = \"discriminant\")]`, explicit discriminants must be non-negative integer literals.";
+static BIT_WITH_DISCRIMINANT_MSG: &str = "`#[bitset(bit = N)]` cannot be used with `#[bitset(bits = \"discriminant\")]`: the bit position of every variant is its discriminant.";
+
+
+/// How the bit positions of the variants are assigned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitLayout {
+    /// Following the declaration order, unless pinned with `#[bitset(bit = N)]`.
+    Declaration,
+    /// The bit position of each variant is its discriminant.
+    Discriminant,
+}
+
+
+/// A variant of the base enum, as seen by the bitset.
+pub struct BitsetVariant {
+    pub ident: Ident,
+    /// Position of the bit that represents the variant.
+    pub bit: usize,
+    /// Names of the groups the variant belongs to, from `#[bitset(group(..))]`.
+    pub groups: Vec<Ident>,
+    /// Name given with `#[bitset(rename = "..")]`, which overrides `rename_all`.
+    pub rename: Option<String>,
+}
+
+
+/// A variant of the base enum excluded from the bitset with `#[bitset(skip)]`.
+pub struct SkippedVariant {
+    pub ident: Ident,
+    /// Name given with `#[bitset(rename = "..")]`, which overrides `rename_all`.
+    pub rename: Option<String>,
+}
+
+
+/// The options of a variant, from its `#[bitset(..)]` attributes.
+#[derive(Default)]
+struct VariantAttrs {
+    bit: Option<usize>,
+    skip: bool,
+    groups: Vec<Ident>,
+    rename: Option<String>,
+}
+
+
+/// Parses the variants of the base enum, assigning a bit position to each of them, and
+/// separating the ones marked with `#[bitset(skip)]`, which get no bit at all.
+///
+/// Variants take the position given by their `#[bitset(bit = N)]` attribute (or by their explicit
+/// discriminant, with [`BitLayout::Discriminant`]), if any. Otherwise, they take the position that
+/// follows the one of the previous variant (or 0, for the first one), the same way Rust assigns
+/// implicit discriminants. Skipped variants do not take a position, except for the value of
+/// their discriminant with [`BitLayout::Discriminant`], which is followed by the next variant.
+pub fn parse_variants(
+    variants: impl IntoIterator<Item = Variant>,
+    layout: BitLayout,
+) -> Result<(Vec<BitsetVariant>, Vec<SkippedVariant>)> {
+    let mut parsed: Vec<BitsetVariant> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut next_bit = 0;
+
+    for variant in variants {
+        let mut attrs = VariantAttrs::default();
+
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("bitset"))
+        {
+            attr.parse_nested_meta(|meta| attrs.parse(&meta))?;
+        }
+
+        let VariantAttrs {
+            bit: mut explicit_bit,
+            skip,
+            groups,
+            rename,
+        } = attrs;
+
+        if skip && explicit_bit.is_some() {
+            return Err(Error::new(variant.span(), BIT_WITH_SKIP_MSG));
+        }
+
+        if skip && !groups.is_empty() {
+            return Err(Error::new(variant.span(), GROUP_WITH_SKIP_MSG));
+        }
+
+        if !skip && !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(variant.span(), ALL_UNIT_MSG));
+        }
+
+        if layout == BitLayout::Discriminant {
+            if explicit_bit.is_some() {
+                return Err(Error::new(variant.span(), BIT_WITH_DISCRIMINANT_MSG));
+            }
+
+            if let Some((_, discriminant)) = &variant.discriminant {
+                explicit_bit = Some(parse_discriminant(discriminant)?);
+            }
+        }
+
+        let bit = explicit_bit.unwrap_or(next_bit);
+
+        if skip {
+            if layout == BitLayout::Discriminant {
+                next_bit = bit.saturating_add(1);
+            }
+            skipped.push(SkippedVariant {
+                ident: variant.ident,
+                rename,
+            });
+            continue;
+        }
+
+        if let Some(other) = parsed.iter().find(|other| other.bit == bit) {
+            return Err(Error::new(
+                variant.span(),
+                format!(
+                    "Duplicate bit position {bit}: both `{}` and `{}` are assigned to it.",
+                    other.ident, variant.ident
+                ),
+            ));
+        }
+
+        next_bit = bit.saturating_add(1);
+        parsed.push(BitsetVariant {
+            ident: variant.ident,
+            bit,
+            groups,
+            rename,
+        });
+    }
+
+    Ok((parsed, skipped))
+}
+
+
+fn parse_discriminant(discriminant: &Expr) -> Result<usize> {
+    match discriminant {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) => value
+            .base10_parse::<u32>()
+            .map(|value| value as usize)
+            .map_err(|_| Error::new(value.span(), INVALID_DISCRIMINANT_MSG)),
+        _ => Err(Error::new(discriminant.span(), INVALID_DISCRIMINANT_MSG)),
+    }
+}
+
+
+impl VariantAttrs {
+    fn parse(&mut self, meta: &ParseNestedMeta) -> Result<()> {
+        if meta.path.is_ident("bit") {
+            let value: LitInt = meta.value()?.parse()?;
+            let value: u32 = value.base10_parse()?;
+            self.bit = Some(value as usize);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("skip") {
+            self.skip = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("group") {
+            return meta.parse_nested_meta(|group| {
+                let name = group.path.require_ident()?;
+                if !self.groups.contains(name) {
+                    self.groups.push(name.clone());
+                }
+                Ok(())
+            });
+        }
+
+        if meta.path.is_ident("rename") {
+            let value: LitStr = meta.value()?.parse()?;
+            self.rename = Some(value.value());
+            return Ok(());
+        }
+
+        Err(meta.error(INVALID_VARIANT_ATTR_MSG))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use syn::{DeriveInput, parse_str};
+
+    use super::*;
+
+    fn bits(input: &str) -> Result<Vec<usize>> {
+        layout_bits(input, BitLayout::Declaration)
+    }
+
+    fn layout_bits(input: &str, layout: BitLayout) -> Result<Vec<usize>> {
+        let input: DeriveInput = parse_str(input).unwrap();
+        let syn::Data::Enum(data) = input.data else {
+            unreachable!()
+        };
+
+        Ok(parse_variants(data.variants, layout)?
+            .0
+            .into_iter()
+            .map(|variant| variant.bit)
+            .collect())
+    }
+
+    #[test]
+    fn implicit_bits() {
+        assert_eq!(bits("enum E { A, B, C }").unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn explicit_bits() {
+        let input = "enum E { #[bitset(bit = 3)] A, B, #[bitset(bit = 0)] C }";
+        assert_eq!(bits(input).unwrap(), [3, 4, 0]);
+    }
+
+    #[test]
+    fn duplicate_bits() {
+        let input = "enum E { A, B, #[bitset(bit = 1)] C }";
+        let err = bits(input).unwrap_err();
+        assert!(err.to_string().contains("Duplicate bit position 1"));
+    }
+
+    #[test]
+    fn invalid_attr() {
+        assert!(bits("enum E { #[bitset(nope)] A }").is_err());
+        assert!(bits("enum E { #[bitset(bit = -1)] A }").is_err());
+    }
+
+    #[test]
+    fn discriminant_bits() {
+        let input = "enum E { Read = 0, Write = 1, Exec = 5, Admin }";
+        assert_eq!(layout_bits(input, BitLayout::Discriminant).unwrap(), [0, 1, 5, 6]);
+        assert_eq!(bits(input).unwrap(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn invalid_discriminants() {
+        let negative = "enum E { A = -1 }";
+        assert!(layout_bits(negative, BitLayout::Discriminant).is_err());
+
+        let expression = "enum E { A = 1 << 3 }";
+        assert!(layout_bits(expression, BitLayout::Discriminant).is_err());
+
+        let duplicate = "enum E { A = 2, B = 1, C }";
+        assert!(layout_bits(duplicate, BitLayout::Discriminant).is_err());
+
+        let with_bit = "enum E { #[bitset(bit = 3)] A = 0 }";
+        assert!(layout_bits(with_bit, BitLayout::Discriminant).is_err());
+    }
+
+    #[test]
+    fn skipped_variants() {
+        let input = "enum E { A, #[bitset(skip)] Unknown(u8), B, #[bitset(skip)] Other { x: u8 } }";
+        assert_eq!(bits(input).unwrap(), [0, 1]);
+
+        let input = "enum E { A = 0, #[bitset(skip)] Unknown = 4, B }";
+        assert_eq!(layout_bits(input, BitLayout::Discriminant).unwrap(), [0, 5]);
+
+        assert!(bits("enum E { A, Unknown(u8) }").is_err());
+        assert!(bits("enum E { A, #[bitset(skip, bit = 3)] B }").is_err());
+    }
+
+    #[test]
+    fn groups() {
+        let input = "enum E { #[bitset(group(read, write))] A, #[bitset(group(read), group(read))] B, C }";
+        let input: DeriveInput = parse_str(input).unwrap();
+        let syn::Data::Enum(data) = input.data else {
+            unreachable!()
+        };
+
+        let (variants, _) = parse_variants(data.variants, BitLayout::Declaration).unwrap();
+        assert_eq!(variants[0].groups, ["read", "write"]);
+        assert_eq!(variants[1].groups, ["read"]);
+        assert!(variants[2].groups.is_empty());
+
+        assert!(bits("enum E { A, #[bitset(skip, group(read))] B }").is_err());
+        assert!(bits("enum E { #[bitset(group(a::b))] A }").is_err());
+    }
+}
@@ -6,34 +6,96 @@ use crate::derive::config::EnumBitsetConfig;
 
 impl EnumBitsetConfig {
     pub fn derive_core_traits(&self) -> TokenStream2 {
-        let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let name = self.set_ty();
+        let base_ty = self.base_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
 
         let to_value_branches = self.base_to_value_branches();
 
         quote! {
-            impl Default for #name {
+            impl #impl_generics Default for #name #where_clause {
                 #[inline(always)]
                 fn default() -> Self {
-                    Self { items: 0 }
+                    Self { items: bits::empty(), marker: PhantomData }
                 }
             }
 
-            impl From<#base_ty> for #name {
+            impl #impl_generics From<#base_ty> for #name #where_clause {
                 #[inline(always)]
                 fn from(item: #base_ty) -> Self {
                     (&item).into()
                 }
             }
 
-            impl From<&#base_ty> for #name {
+            impl #impl_generics From<&#base_ty> for #name #where_clause {
                 #[inline(always)]
                 fn from(item: &#base_ty) -> Self {
                     let items = match item {
                         #(#to_value_branches),*
                     };
 
-                    Self { items }
+                    Self { items, marker: PhantomData }
+                }
+            }
+        }
+    }
+
+    /// Implementations of the traits that non-generic sets derive. `derive` would add a bound on
+    /// every generic parameter of the base enum, which the set does not need.
+    pub fn impl_generic_traits(&self) -> TokenStream2 {
+        if !self.is_generic() {
+            return TokenStream2::new();
+        }
+
+        let name = self.set_ty();
+        let iter = self.iter_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let hasher = self.param("H");
+
+        quote! {
+            impl #impl_generics Clone for #name #where_clause {
+                #[inline(always)]
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl #impl_generics Copy for #name #where_clause {}
+
+            impl #impl_generics PartialEq for #name #where_clause {
+                #[inline(always)]
+                fn eq(&self, other: &Self) -> bool {
+                    bits::eq(self.items, other.items)
+                }
+            }
+
+            impl #impl_generics Eq for #name #where_clause {}
+
+            impl #impl_generics ::core::hash::Hash for #name #where_clause {
+                #[inline]
+                fn hash<#hasher: ::core::hash::Hasher>(&self, state: &mut #hasher) {
+                    ::core::hash::Hash::hash(&self.items, state)
+                }
+            }
+
+            impl #impl_generics PartialOrd for #name #where_clause {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                    Option::Some(self.cmp(other))
+                }
+            }
+
+            impl #impl_generics Ord for #name #where_clause {
+                #[inline]
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    self.items.cmp(&other.items)
+                }
+            }
+
+            impl #impl_generics Clone for #iter #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    Self { items: self.items, marker: PhantomData }
                 }
             }
         }
@@ -45,15 +107,19 @@ impl EnumBitsetConfig {
         }
 
         let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let set_ty = self.set_ty();
+        let base_ty = self.base_ty();
         let my_crate = &self.my_crate;
+        let (impl_generics, _, _) = self.generics.split_for_impl();
+        // Without the `Debug` bound, `DebugWrapper` would never see the base enum as `Debug`
+        let where_clause = self.where_clause_with(quote!(Clone + Debug));
 
         let name_lit = LitStr::new(&name.to_string(), Span::call_site());
 
         quote! {
             use #my_crate::debug_impl::{NoDebug, DebugWrapper};
 
-            impl Debug for #name {
+            impl #impl_generics Debug for #set_ty #where_clause {
                 fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
                     f.write_str(#name_lit)?;
 
@@ -0,0 +1,117 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Error, LitStr, Result, meta::ParseNestedMeta, token::Paren};
+
+use crate::derive::config::EnumBitsetConfig;
+
+static INVALID_DISPLAY_MSG: &str =
+    "Invalid value for display. Expected `display`, or `display(sep = \"..\")`.";
+static EMPTY_DISPLAY_SEP_MSG: &str = "The separator given by `display(sep = \"..\")` cannot be empty";
+
+
+/// Configuration of the `Display` and `FromStr` implementations, requested with
+/// `#[bitset(display)]`.
+pub struct DisplayConfig {
+    /// Written between the names of the variants. When parsing, whitespace around it is ignored.
+    pub sep: String,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig { sep: " | ".into() }
+    }
+}
+
+impl DisplayConfig {
+    /// Parses `display`, or `display(sep = "..")`.
+    pub fn parse(meta: &ParseNestedMeta) -> Result<Self> {
+        let mut config = Self::default();
+
+        if meta.input.peek(Paren) {
+            meta.parse_nested_meta(|inner| {
+                if inner.path.is_ident("sep") {
+                    let sep = inner.value()?.parse::<LitStr>()?;
+                    if sep.value().is_empty() {
+                        return Err(Error::new(sep.span(), EMPTY_DISPLAY_SEP_MSG));
+                    }
+                    config.sep = sep.value();
+                    return Ok(());
+                }
+                Err(inner.error(INVALID_DISPLAY_MSG))
+            })?;
+        }
+
+        Ok(config)
+    }
+}
+
+
+impl EnumBitsetConfig {
+    pub fn impl_display(&self) -> TokenStream2 {
+        let Some(display) = &self.display else {
+            return TokenStream2::new();
+        };
+
+        let set_ty = self.set_ty();
+        let my_crate = &self.my_crate;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let sep = &display.sep;
+
+        let write_names = self.write_names(&quote!(self.items), sep);
+        let parse_branches = self.name_to_bit_branches();
+
+        quote! {
+            impl #impl_generics Display for #set_ty #where_clause {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    #write_names
+                }
+            }
+
+            impl #impl_generics ::core::str::FromStr for #set_ty #where_clause {
+                type Err = #my_crate::ParseSetError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let mut items = bits::empty();
+
+                    for token in #my_crate::display_impl::Tokens::new(s, #sep) {
+                        let bit = match token {
+                            #(#parse_branches,)*
+                            _ => return Err(#my_crate::ParseSetError::unknown(token)),
+                        };
+                        items = bits::or(items, bit);
+                    }
+
+                    Ok(Self { items, marker: PhantomData })
+                }
+            }
+        }
+    }
+
+    /// Statements that write the names of the variants in `items` to `f`, separated by `sep`.
+    pub(crate) fn write_names(&self, items: &TokenStream2, sep: &str) -> TokenStream2 {
+        // Sorted by bit, to write them in the same order as the iterator returns them
+        let mut variants: Vec<_> = self.variants.iter().collect();
+        variants.sort_by_key(|variant| variant.bit);
+
+        let write_variants = variants.iter().map(|variant| {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+            let bit = variant.bit as u32;
+
+            quote! {
+                if !bits::is_empty(bits::and(#items, bits::bit(#bit))) {
+                    if !first {
+                        f.write_str(#sep)?;
+                    }
+                    first = false;
+                    f.write_str(#name)?;
+                }
+            }
+        });
+
+        quote! {
+            let mut first = true;
+            #(#write_variants)*
+            Ok(())
+        }
+    }
+}
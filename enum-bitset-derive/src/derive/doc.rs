@@ -1,24 +1,43 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::derive::config::EnumBitsetConfig;
+use crate::derive::config::{EnumBitsetConfig, Repr};
 
 impl EnumBitsetConfig {
     pub fn set_docs(&self) -> TokenStream {
         let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let inner_ty = &self.inner_type;
         let len = self.variants.len();
 
         let doc1 = format!("A set of [`{base_ty}`] values, efficiently implemented as a bitfield.");
-        let doc2 =
-            format!("It is internally implemented as a single [`{inner_ty}`] integer value.");
-        let doc3 = format!(
-            "Only the last {len} (the number of variants in [`{base_ty}`]) may be non-zero."
-        );
+        let doc2 = match inner_ty {
+            Repr::Int(_) => {
+                format!("It is internally implemented as a single [`{inner_ty}`] integer value.")
+            }
+            Repr::Words(_) => format!(
+                "It is internally implemented as an array of `u64` words, `{inner_ty}`, where the first word holds the 64 least significant bits."
+            ),
+        };
+        let doc3 = if self.is_dense() {
+            format!("Only the last {len} (the number of variants in [`{base_ty}`]) may be non-zero.")
+        } else {
+            format!("Only the {len} bits assigned to a variant of [`{base_ty}`] may be non-zero.")
+        };
         let doc4 = format!(
-            "It is guaranteed that the layout and ABI of a [`{name}`] is exactly the same of a [`{inner_ty}`]."
+            "It is guaranteed that the layout and ABI of a [`{name}`] is exactly the same of a `{inner_ty}`."
         );
+        let layout = if self.is_dense() {
+            "The N-th least significant bit of the value is set to `1` if the N-th variant is present in the set, and `0` otherwise.".to_string()
+        } else {
+            let positions = self
+                .variants
+                .iter()
+                .map(|variant| format!("[`{}`]({base_ty}::{}) is bit {}", variant.ident, variant.ident, variant.bit))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("Every variant has a pinned bit position, which is set to `1` if the variant is present in the set, and `0` otherwise: {positions}.")
+        };
         let doc5 =
             format!("This is true regardless of any `#[repr(..)]` attribute set on [`{base_ty}`].");
 
@@ -26,7 +45,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc1]
                 ///
                 #[doc = #doc2]
-                /// The N-th least significant bit of the value is set to `1` if the N-th variant is present in the set, and `0` otherwise.
+                #[doc = #layout]
                 ///
                 /// # Representation
                 #[doc = #doc4]
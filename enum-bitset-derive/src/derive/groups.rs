@@ -0,0 +1,138 @@
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Error, Result};
+
+use crate::derive::config::{BitsetVariant, EnumBitsetConfig};
+
+/// Associated constants that the set already has, so no group can be named like them.
+const RESERVED: [&str; 3] = ["MASK", "VARIANTS", "NAMES"];
+
+impl EnumBitsetConfig {
+    /// Checks that every group gets a constant of its own: its name must not clash with the
+    /// constants of the set, nor with a differently spelled group, like `read_only` and
+    /// `readOnly`.
+    pub(crate) fn check_groups(&self) -> Result<()> {
+        let mut seen: Vec<&Ident> = Vec::new();
+
+        for group in self.variants.iter().flat_map(|variant| &variant.groups) {
+            let name = group.to_string().to_shouty_snake_case();
+
+            if RESERVED.contains(&name.as_str()) {
+                return Err(Error::new(group.span(), format!(
+                    "Invalid group name `{group}`: the set already has a constant named `{name}`."
+                )));
+            }
+
+            if let Some(other) = seen
+                .iter()
+                .find(|other| **other != group && other.to_string().to_shouty_snake_case() == name)
+            {
+                return Err(Error::new(group.span(), format!(
+                    "Conflicting group names `{other}` and `{group}`: both would be named `{name}`."
+                )));
+            }
+
+            if !seen.contains(&group) {
+                seen.push(group);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The groups declared with `#[bitset(group(..))]`, in order of first appearance, along with
+    /// their variants. `check_groups` already made sure that every group has its own constant.
+    pub(crate) fn groups(&self) -> Vec<(Ident, Vec<&BitsetVariant>)> {
+        let mut groups: Vec<(Ident, Vec<&BitsetVariant>)> = Vec::new();
+
+        for variant in &self.variants {
+            for group in &variant.groups {
+                let name = group.to_string().to_shouty_snake_case();
+                let name = format_ident!("{name}", span = group.span());
+
+                match groups
+                    .iter()
+                    .position(|(other, _)| *other == name)
+                {
+                    Some(position) => groups[position].1.push(variant),
+                    None => groups.push((name, vec![variant])),
+                }
+            }
+        }
+
+        groups
+    }
+
+    /// One associated constant per group, named after the group in `SCREAMING_SNAKE_CASE`.
+    pub(crate) fn impl_groups(&self) -> TokenStream2 {
+        let groups = self.groups();
+        if groups.is_empty() {
+            return TokenStream2::new();
+        }
+
+        let set_ty = self.set_ty();
+        let base = self.base_name();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
+        let consts = groups.iter().map(|(name, variants)| {
+            let mask = self.mask_of(variants.iter().copied());
+            let members = variants
+                .iter()
+                .map(|variant| format!("[`{0}`]({base}::{0})", variant.ident))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let doc = format!("The variants in the `{name}` group: {members}.");
+
+            quote! {
+                #[doc = #doc]
+                pub const #name: Self = Self { items: #mask, marker: PhantomData };
+            }
+        });
+
+        quote! {
+            impl #impl_generics #set_ty #where_clause {
+                #(#consts)*
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use syn::{DeriveInput, parse_str};
+
+    use super::*;
+
+    fn parse(input: &str) -> Result<EnumBitsetConfig> {
+        let input: DeriveInput = parse_str(input)?;
+        input.try_into()
+    }
+
+    #[test]
+    fn group_names() {
+        let config = parse("enum E { #[bitset(group(read_only))] A, #[bitset(group(read_only, all_of_them))] B }").unwrap();
+        let groups = config.groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "READ_ONLY");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "ALL_OF_THEM");
+    }
+
+    #[test]
+    fn reserved_group_names() {
+        assert!(parse("enum E { #[bitset(group(mask))] A }").is_err());
+        assert!(parse("enum E { #[bitset(group(Variants))] A }").is_err());
+        assert!(parse("enum E { #[bitset(group(names))] A }").is_err());
+
+        // `all` and `empty` are methods, so constants with those names do not clash
+        assert!(parse("enum E { #[bitset(group(all))] A, #[bitset(group(empty))] B }").is_ok());
+    }
+
+    #[test]
+    fn conflicting_group_names() {
+        assert!(parse("enum E { #[bitset(group(read_only))] A, #[bitset(group(readOnly))] B }").is_err());
+        assert!(parse("enum E { #[bitset(group(read_only, ReadOnly))] A }").is_err());
+    }
+}
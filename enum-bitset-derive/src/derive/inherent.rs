@@ -5,8 +5,6 @@ use crate::derive::config::EnumBitsetConfig;
 
 impl EnumBitsetConfig {
     pub fn impl_inherent(&self) -> TokenStream2 {
-        let name = &self.set_type;
-
         let items = [
             self.impl_mask(),
             self.impl_variants(),
@@ -31,6 +29,7 @@ impl EnumBitsetConfig {
             self.impl_is_disjoint(),
             self.impl_is_complementary(),
             self.impl_insert(),
+            self.impl_try_insert(),
             self.impl_remove(),
             self.impl_insert_const(),
             self.impl_remove_const(),
@@ -44,8 +43,11 @@ impl EnumBitsetConfig {
             self.impl_collect(),
         ];
 
+        let set_ty = self.set_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
         quote! {
-            impl #name {
+            impl #impl_generics #set_ty #where_clause {
                 #(#items)*
             }
         }
@@ -54,8 +56,7 @@ impl EnumBitsetConfig {
 
     fn impl_mask(&self) -> TokenStream2 {
         let inner_ty = &self.inner_type;
-        let len = self.len();
-        let mask = Self::mask(len);
+        let mask = self.mask_tokens();
 
         quote! {
             #[doc(hidden)]
@@ -65,12 +66,13 @@ impl EnumBitsetConfig {
 
 
     fn impl_variants(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base_type = &self.base_type;
+        let base_ty = self.base_ty();
         let len = self.len();
 
         let array_items = self.variants.iter().map(move |variant| {
             let name = &variant.ident;
-            quote! {#base_ty::#name}
+            quote! {#base_type::#name}
         });
 
         quote!(
@@ -90,15 +92,18 @@ impl EnumBitsetConfig {
         quote!(
             #[doc = #doc]
             pub const fn new() -> Self {
-                Self { items: 0 }
+                Self { items: bits::empty(), marker: PhantomData }
             }
         )
     }
 
 
     fn impl_from(&self) -> TokenStream2 {
+        let base = self.base_ty();
+        let iter_param = self.param("T");
+        let item_param = self.param("I");
         let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
 
         let doc = format!(
             r#"Creates a new [`{name}`] from an iterator that yields any borrowed type of 
@@ -107,19 +112,19 @@ impl EnumBitsetConfig {
 
         quote!(
                #[doc = #doc]
-                pub fn from<T: IntoIterator<Item = I>, I: Borrow<#base_ty>>(iter: T) -> Self {
+                pub fn from<#iter_param: IntoIterator<Item = #item_param>, #item_param: Borrow<#base>>(iter: #iter_param) -> Self {
                     let items = iter.into_iter()
-                                    .fold(0, |acc, item| acc | base_to_value(item.borrow()));
+                                    .fold(bits::empty(), |acc, item| bits::or(acc, base_to_value(item.borrow())));
 
-                    Self{ items }
+                    Self { items, marker: PhantomData }
                 }
         )
     }
 
 
     fn impl_from_slice(&self) -> TokenStream2 {
+        let base = self.base_ty();
         let name = &self.set_type;
-        let base_ty = &self.base_type;
         let doc = format!(
             r#"Creates a new [`{name}`] from a slice of the variants of the set. Can be used in 
                const contexts."#
@@ -127,24 +132,25 @@ impl EnumBitsetConfig {
 
         quote!(
                 #[doc = #doc]
-                pub const fn from_slice(slice: &[#base_ty]) -> Self {
-                    let mut items = 0;
+                pub const fn from_slice(slice: &[#base]) -> Self {
+                    let mut items = bits::empty();
                     let mut index = 0;
 
                     while index < slice.len() {
-                        items |= base_to_value(&slice[index]);
+                        items = bits::or(items, base_to_value(&slice[index]));
                         index += 1;
                     }
 
-                    Self{ items }
+                    Self { items, marker: PhantomData }
                 }
         )
     }
 
 
     fn impl_from_array(&self) -> TokenStream2 {
+        let base = self.base_ty();
+        let len = self.param("N");
         let name = &self.set_type;
-        let base_ty = &self.base_type;
 
         let doc = format!(
             r#"Creates a new [`{name}`] from an array of the variants of the set. Can be used in 
@@ -153,16 +159,21 @@ impl EnumBitsetConfig {
 
         quote!(
                 #[doc = #doc]
-                pub const fn from_array<const N: usize>(array: [#base_ty; N]) -> Self {
-                    let mut items = 0;
+                pub const fn from_array<const #len: usize>(array: [#base; #len]) -> Self {
+                    let mut items = bits::empty();
                     let mut index = 0;
 
-                    while index < N {
-                        items |= base_to_value(&array[index]);
+                    while index < #len {
+                        items = bits::or(items, base_to_value(&array[index]));
                         index += 1;
                     }
 
-                    Self{ items }
+                    // The array cannot be dropped in const contexts if the base enum may need
+                    // drop (i.e. it is generic, or has skipped variants with fields). There is
+                    // nothing to drop in the variants that the set may contain anyway.
+                    let _ = ::core::mem::ManuallyDrop::new(array);
+
+                    Self { items, marker: PhantomData }
                 }
         )
     }
@@ -175,7 +186,7 @@ impl EnumBitsetConfig {
         quote!(
                 #[doc = #doc]
                 pub const fn empty() -> Self {
-                    Self { items: 0 }
+                    Self { items: bits::empty(), marker: PhantomData }
                 }
         )
     }
@@ -183,14 +194,14 @@ impl EnumBitsetConfig {
 
     fn impl_all(&self) -> TokenStream2 {
         let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc =
             format!("Creates a new [`{name}`] that contains all the variants of [`{base_ty}`].");
 
         quote!(
                 #[doc = #doc]
                 pub const fn all() -> Self {
-                    Self { items: Self::MASK }
+                    Self { items: Self::MASK, marker: PhantomData }
                 }
         )
     }
@@ -201,14 +212,14 @@ impl EnumBitsetConfig {
             /// Returns `true` if the set is empty.
             #[inline]
             pub const fn is_empty(&self) -> bool {
-                self.items == 0
+                bits::is_empty(self.items)
             }
         )
     }
 
 
     fn impl_is_all(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let len = self.len();
         let doc = if len > 1 {
             format!(
@@ -222,7 +233,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc]
                 #[inline]
                 pub const fn is_all(&self) -> bool {
-                    self.items == Self::MASK
+                    bits::eq(self.items, Self::MASK)
                 }
         )
     }
@@ -230,21 +241,38 @@ impl EnumBitsetConfig {
 
 
     fn impl_len(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc = format!("Returns the number of variants of [`{base_ty}`] present in the set.");
 
         quote!(
                 #[doc = #doc]
                 #[inline]
                 pub const fn len(&self) -> usize {
-                    self.items.count_ones() as usize
+                    bits::count_ones(self.items) as usize
                 }
         )
     }
 
 
     fn impl_contains(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
+        let item_param = self.param("T");
+        let base_ty = self.base_name();
+
+        if let Some(kinds_of) = &self.kinds_of {
+            let doc = format!(
+                r#"Returns `true` if the set contains the given variant, by value or by reference. A 
+                   reference to a [`{kinds_of}`] value checks its [`kind`]({kinds_of}::kind)."#
+            );
+
+            return quote!(
+                    #[doc = #doc]
+                    #[inline]
+                    pub fn contains<#item_param: Into<#base>>(&self, item: #item_param) -> bool {
+                        !bits::is_empty(bits::and(self.items, base_to_value(&item.into())))
+                    }
+            );
+        }
         let doc = format!(
             r#"Returns `true` if the set contains the given variant. The variant can be specified by 
                any borrow of [`{base_ty}`]."#
@@ -253,16 +281,17 @@ impl EnumBitsetConfig {
         quote!(
                 #[doc = #doc]
                 #[inline]
-                pub fn contains<T: Borrow<#base_ty >>(&self, item: T) -> bool {
-                    self.items & base_to_value(item.borrow()) != 0
+                pub fn contains<#item_param: Borrow<#base>>(&self, item: #item_param) -> bool {
+                    !bits::is_empty(bits::and(self.items, base_to_value(item.borrow())))
                 }
         )
     }
 
 
     fn impl_contains_const(&self) -> TokenStream2 {
+        let base = self.base_ty();
         let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc = format!(
             r#"Returns `true` if the set contains the given variant. This version of 
                [`contains`]({name}::contains) can be used in const contexts, but does 
@@ -272,8 +301,8 @@ impl EnumBitsetConfig {
         quote!(
                 #[doc = #doc]
                 #[inline]
-                pub fn contains_const(&self, item: &#base_ty) -> bool {
-                    self.items & base_to_value(item) != 0
+                pub fn contains_const(&self, item: &#base) -> bool {
+                    !bits::is_empty(bits::and(self.items, base_to_value(item)))
                 }
         )
     }
@@ -290,7 +319,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc]
                 #[inline]
                 pub const fn union(&self, other: &Self) -> Self {
-                    Self { items: self.items | other.items }
+                    Self { items: bits::or(self.items, other.items), marker: PhantomData }
                 }
         )
     }
@@ -307,7 +336,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc]
                 #[inline]
                 pub const fn intersection(&self, other: &Self) -> Self {
-                    Self { items: self.items & other.items }
+                    Self { items: bits::and(self.items, other.items), marker: PhantomData }
                 }
         )
     }
@@ -324,7 +353,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc]
                 #[inline]
                 pub const fn difference(&self, other: &Self) -> Self {
-                    Self { items: self.items & !other.items }
+                    Self { items: bits::and_not(self.items, other.items), marker: PhantomData }
                 }
         )
     }
@@ -340,7 +369,7 @@ impl EnumBitsetConfig {
                 #[doc = #doc]
                 #[inline]
                 pub const fn symmetric_difference(&self, other: &Self) -> Self {
-                    Self { items: self.items ^ other.items }
+                    Self { items: bits::xor(self.items, other.items), marker: PhantomData }
                 }
         )
     }
@@ -358,7 +387,7 @@ impl EnumBitsetConfig {
             #[doc = #doc]
             #[inline]
             pub const fn complement(&self) -> Self {
-                Self { items: Self::MASK & !self.items }
+                Self { items: bits::and_not(Self::MASK, self.items), marker: PhantomData }
             }
         )
     }
@@ -370,7 +399,7 @@ impl EnumBitsetConfig {
             /// the items that exist in `other`.
             #[inline]
             pub fn is_subset_of(&self, other: &Self) -> bool {
-                (self.items & other.items) == self.items
+                bits::eq(bits::and(self.items, other.items), self.items)
             }
         )
     }
@@ -381,7 +410,7 @@ impl EnumBitsetConfig {
             /// the items that exist in `self`.
             #[inline]
             pub fn is_superset_of(&self, other: &Self) -> bool {
-                (self.items & other.items) == other.items
+                bits::eq(bits::and(self.items, other.items), other.items)
             }
         )
     }
@@ -392,7 +421,7 @@ impl EnumBitsetConfig {
             /// Returns `true` if `self` has no elements in common with `other`.
             #[inline]
             pub const fn is_disjoint(&self, other: &Self) -> bool {
-                (self.items & other.items) == 0
+                bits::is_empty(bits::and(self.items, other.items))
             }
         )
     }
@@ -403,95 +432,120 @@ impl EnumBitsetConfig {
             /// Two sets are complementary if their union contains all variants and their intersection is empty.
             #[inline]
             pub const fn is_complementary(&self, other: &Self) -> bool {
-                self.is_disjoint(other) && (self.items | other.items) == Self::MASK
+                self.is_disjoint(other) && bits::eq(bits::or(self.items, other.items), Self::MASK)
             }
         )
     }
 
 
     fn impl_insert(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
+        let item_param = self.param("T");
         let doc = format!(
             "Inserts a variant into the set. The variant can be specified by any borrow of [`{}`].",
-            self.base_type
+            self.base_name()
         );
 
         quote!(
             #[doc = #doc]
             #[inline]
-            pub fn insert<T: Borrow<#base_ty >>(&mut self, item: T) {
+            pub fn insert<#item_param: Borrow<#base>>(&mut self, item: #item_param) {
                 self.insert_const(item.borrow());
             }
         )
     }
 
 
+    fn impl_try_insert(&self) -> TokenStream2 {
+        let base = self.base_ty();
+        let item_param = self.param("T");
+        let base_ty = self.base_name();
+        let doc = format!(
+            r#"Inserts a variant into the set, returning `false` if it is a variant skipped with 
+               `#[bitset(skip)]`, that cannot be stored in the set. The variant can be specified by 
+               any borrow of [`{base_ty}`]."#
+        );
+
+        quote!(
+            #[doc = #doc]
+            #[inline]
+            pub fn try_insert<#item_param: Borrow<#base>>(&mut self, item: #item_param) -> bool {
+                let value = base_to_value(item.borrow());
+                self.items = bits::or(self.items, value);
+                !bits::is_empty(value)
+            }
+        )
+    }
+
+
     fn impl_remove(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
+        let item_param = self.param("T");
         let doc = format!(
             "Removes a variant from the set. The variant can be specified by any borrow of [`{}`].",
-            self.base_type
+            self.base_name()
         );
 
         quote!(
             #[doc = #doc]
             #[inline]
-            pub fn remove<T: Borrow<#base_ty >>(&mut self, item: T) {
+            pub fn remove<#item_param: Borrow<#base>>(&mut self, item: #item_param) {
                 self.remove_const(item.borrow());
             }
         )
     }
 
     fn impl_insert_const(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
         let name = &self.set_type;
         let doc = format!(
             r#"Inserts a variant into the set. This version of [`insert`]({name}::insert) can be 
                used in const contexts, but does not allow using a borrowed type of [`{}`]."#,
-            self.base_type
+            self.base_name()
         );
 
         quote!(
             #[doc = #doc]
             #[inline]
-            pub const fn insert_const(&mut self, item: &#base_ty) {
-                self.items |= base_to_value(item);
+            pub const fn insert_const(&mut self, item: &#base) {
+                self.items = bits::or(self.items, base_to_value(item));
             }
         )
     }
 
     fn impl_remove_const(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
         let doc = format!(
             r#"Removes a variant from the set. This version of [`remove`]({}::remove) can be used in 
                const contexts, but does not allow using a borrowed type of [`{}`]."#,
-            self.set_type, self.base_type
+            self.set_type, self.base_name()
         );
 
         quote!(
             #[doc = #doc]
             #[inline]
-            pub const fn remove_const(&mut self, item: &#base_ty) {
+            pub const fn remove_const(&mut self, item: &#base) {
                 // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                self.items &= !base_to_value(item);
+                self.items = bits::and_not(self.items, base_to_value(item));
             }
         )
     }
 
     fn impl_iter_inherent(&self) -> TokenStream2 {
+        let iter_ty = self.iter_ty();
         let iter = &self.iter_type;
         let doc = format!(
             r#"Returns an iterator over the variants of [`{}`] contained in the set. Variants are 
                yielded in the order in which they appear in the definition of [`{}`], regardless 
                of insertion order."#,
-            self.base_type, self.base_type
+            self.base_name(), self.base_name()
         );
 
         quote!(
             #[doc = #doc]
             #[inline]
-            pub const fn iter(&self) -> #iter {
-                #iter { items: self.items }
+            pub const fn iter(&self) -> #iter_ty {
+                #iter { items: self.items, marker: PhantomData }
             }
         )
     }
@@ -499,7 +553,7 @@ impl EnumBitsetConfig {
 
     fn impl_to_repr(&self) -> TokenStream2 {
         let inner_ty = &self.inner_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc = format!(
             r#"Returns the integer representation of the set as a bitset. The N-th variant of 
                [`{base_ty}`] corresponds to the N-th least significative bit of the integer."#
@@ -516,7 +570,7 @@ impl EnumBitsetConfig {
     fn impl_from_repr(&self) -> TokenStream2 {
         let name = &self.set_type;
         let inner_ty = &self.inner_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc1 = format!(
             r#"Creates a new [`{name}`] from an integer interpreted as a bitset. The N-th variant 
                of [`{base_ty}`] corresponds to the N-th least significative bit of the integer."#
@@ -531,9 +585,8 @@ impl EnumBitsetConfig {
             ///
             #[doc = #doc2]
             pub const fn from_repr(repr: #inner_ty) -> Option<Self> {
-                let items = repr & Self::MASK;
-                if items == repr {
-                    Some(Self { items : items & Self::MASK })
+                if Self::is_valid_repr(repr) {
+                    Some(Self { items: repr, marker: PhantomData })
                 } else {
                     None
                 }
@@ -551,7 +604,7 @@ impl EnumBitsetConfig {
         quote!(
             #[doc = #doc]
             pub const fn is_valid_repr(repr: #inner_ty) -> bool {
-                (repr & Self::MASK) == repr
+                bits::eq(bits::and(repr, Self::MASK), repr)
             }
         )
     }
@@ -559,7 +612,7 @@ impl EnumBitsetConfig {
     fn impl_from_repr_unchecked(&self) -> TokenStream2 {
         let name = &self.set_type;
         let inner_ty = &self.inner_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc1 = format!(
             r#"Creates a new [`{name}`] from an integer interpreted as a bitset. The N-th variant 
                of [`{base_ty}`] corresponds to the N-th least significative bit of the integer."#
@@ -595,7 +648,7 @@ impl EnumBitsetConfig {
             ///
             #[doc = #doc4]
             pub const unsafe fn from_repr_unchecked(repr: #inner_ty) -> Self {
-                Self { items : repr }
+                Self { items: repr, marker: PhantomData }
             }
         )
     }
@@ -603,7 +656,7 @@ impl EnumBitsetConfig {
     fn impl_from_repr_masked(&self) -> TokenStream2 {
         let name = &self.set_type;
         let inner_ty = &self.inner_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc1 = format!(
             r#"Creates a new [`{name}`] from an integer interpreted as a bitset, silently discarding invalid bits.
                The N-th variant of [`{base_ty}`] corresponds to the N-th least significative bit of the integer."#
@@ -615,7 +668,7 @@ impl EnumBitsetConfig {
             /// set to one, they are masked out and ignored to uphold the bitset integrity
             /// invariant.
             pub const unsafe fn from_repr_masked(repr: #inner_ty) -> Self {
-                Self { items : repr & Self::MASK }
+                Self { items: bits::and(repr, Self::MASK), marker: PhantomData }
             }
         )
     }
@@ -623,7 +676,7 @@ impl EnumBitsetConfig {
     fn impl_from_repr_discarded(&self) -> TokenStream2 {
         let name = &self.set_type;
         let inner_ty = &self.inner_type;
-        let base_ty = &self.base_type;
+        let base_ty = self.base_name();
         let doc1 = format!(
             r#"Creates a new [`{name}`] from an integer interpreted as a bitset, discarding invalid bits.
                Returns the created set and an integer where only the discarded bits are set to 1.
@@ -636,14 +689,17 @@ impl EnumBitsetConfig {
             /// set to one, they are masked out to uphold the bitset integrity invariant.
             /// The discarded bits that were set are returned as the second member of the tuple.
             pub const unsafe fn from_repr_discarded(repr: #inner_ty) -> (Self, #inner_ty) {
-                (Self { items : repr & Self::MASK }, repr & !Self::MASK)
+                (Self { items: bits::and(repr, Self::MASK), marker: PhantomData }, bits::and_not(repr, Self::MASK))
             }
         )
     }
 
 
     fn impl_collect(&self) -> TokenStream2 {
-        let base_ty = &self.base_type;
+        let base = self.base_ty();
+        let output = self.param("B");
+        let clone_where = self.is_generic().then(|| quote!(where #base: Clone));
+        let base_ty = self.base_name();
 
         let doc1 = format!(
             "Collects the values present in the set into any value that can be formed by an iterator of [`{base_ty}`]."
@@ -653,7 +709,7 @@ impl EnumBitsetConfig {
         quote! {
             #[doc = #doc1]
             #[doc = #doc2]
-            pub fn collect<B: FromIterator<#base_ty>>(&self) -> B {
+            pub fn collect<#output: FromIterator<#base>>(&self) -> #output #clone_where {
                 self.iter().collect()
             }
         }
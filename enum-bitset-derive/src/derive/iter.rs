@@ -1,4 +1,5 @@
 use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 
 use crate::derive::config::EnumBitsetConfig;
 
@@ -6,88 +7,171 @@ impl EnumBitsetConfig {
     pub fn impl_iter(&self) -> TokenStream2 {
         let name = &self.set_type;
         let iter = &self.iter_type;
-        let base_ty = &self.base_type;
+        let base_type = self.base_name();
+        let set_ty = self.set_ty();
+        let iter_ty = self.iter_ty();
+        let base_ty = self.base_ty();
         let inner_ty = &self.inner_type;
         let inner_vis = &self.inner_vis;
 
+        let struct_generics = &self.generics.params;
+        let struct_where = &self.generics.where_clause;
+        let derive_clone = (!self.is_generic()).then(|| quote!(#[derive(Clone)]));
+
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let clone_where = self.where_clause_with(quote!(Clone));
+        let item = self.lifetime("a");
+        let ref_generics = self.generics_with_lifetime(&item);
+        let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+        let iter_param = self.param("T");
+        let debug_where = self.where_clause_with(quote!(Clone + Debug));
+        let iter_name = iter.to_string();
+
+        let impl_debug = self.debug.then(|| {
+            quote! {
+                impl #impl_generics Debug for #iter_ty #debug_where {
+                    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                        let remaining: #set_ty = #name { items: self.items, marker: PhantomData };
+                        f.debug_tuple(#iter_name).field(&remaining).finish()
+                    }
+                }
+            }
+        });
+
         let doc1 = format!("Iterator returned by the [`iter`]({name}::iter) method.");
         let doc2 = format!(
-            "[`{iter}`] iterates over the variants of [`{base_ty}`] contained in a [`{name}`] instance."
+            "[`{iter}`] iterates over the variants of [`{base_type}`] contained in a [`{name}`] instance."
         );
 
-        quote::quote! {
+        quote! {
             #[doc = #doc1]
             ///
             #[doc = #doc2]
-            #[derive(Clone)]
-            #inner_vis struct #iter {
+            ///
+            /// Variants are returned in the order of their bits, and can be taken from either end.
+            /// `count` and `last` do not walk the iterator. `nth` and `nth_back` skip `n` variants
+            /// by clearing their bits, without creating them, which is still O(n).
+            ///
+            /// `min` and `max` keep their default implementation, which walks the iterator, since
+            /// the `Ord` of the base enum does not need to follow the order of the bits.
+            #derive_clone
+            #inner_vis struct #iter <#struct_generics> #struct_where {
                 items: #inner_ty,
+                marker: PhantomData<fn() -> #base_ty>,
             }
 
-            impl Iterator for #iter {
+            impl #impl_generics Iterator for #iter_ty #clone_where {
                 type Item = #base_ty;
 
                 fn next(&mut self) -> Option<#base_ty> {
-                    if self.items == 0 {
+                    if bits::is_empty(self.items) {
                         return Option::None;
                     }
 
-                    let index = self.items.trailing_zeros();
+                    let index = bits::trailing_zeros(self.items);
 
                     // Safety:
                     // the shift won't panic as it is guaranteed to be <= the size of the inner type.
-                    self.items &= !(1 << index);
+                    self.items = bits::and_not(self.items, bits::bit(index));
 
                     // Safety:
                     // the index op won't panic as long
                     // as the invariant of the items is satisfied
                     // (that is, that only the last N bits are 1).
-                    Option::Some(#name::VARIANTS[index as usize].clone())
+                    Option::Some(<#set_ty>::VARIANTS[bit_to_index(index)].clone())
                 }
 
                 fn size_hint(&self) -> (usize, Option<usize>) {
-                    let n = self.items.count_ones() as usize;
+                    let n = bits::count_ones(self.items) as usize;
                     (n, Option::Some(n))
                 }
 
                 fn count(self) -> usize {
-                    self.items.count_ones() as usize
+                    bits::count_ones(self.items) as usize
+                }
+
+                #[inline]
+                fn last(mut self) -> Option<#base_ty> {
+                    self.next_back()
                 }
+
+                fn nth(&mut self, n: usize) -> Option<#base_ty> {
+                    let n = u32::try_from(n).unwrap_or(u32::MAX);
+                    self.items = bits::clear_lowest(self.items, n);
+                    self.next()
+                }
+
             }
 
-            impl FromIterator<#base_ty> for #name {
-                fn from_iter<T: IntoIterator<Item = #base_ty>>(iter: T) -> Self {
+            impl #impl_generics DoubleEndedIterator for #iter_ty #clone_where {
+                fn next_back(&mut self) -> Option<#base_ty> {
+                    if bits::is_empty(self.items) {
+                        return Option::None;
+                    }
+
+                    let index = bits::highest(self.items);
+                    self.items = bits::and_not(self.items, bits::bit(index));
+
+                    Option::Some(<#set_ty>::VARIANTS[bit_to_index(index)].clone())
+                }
+
+                fn nth_back(&mut self, n: usize) -> Option<#base_ty> {
+                    let n = u32::try_from(n).unwrap_or(u32::MAX);
+                    self.items = bits::clear_highest(self.items, n);
+                    self.next_back()
+                }
+            }
+
+            impl #impl_generics ExactSizeIterator for #iter_ty #clone_where {}
+
+            impl #impl_generics ::core::iter::FusedIterator for #iter_ty #clone_where {}
+
+            #impl_debug
+
+            impl #impl_generics FromIterator<#base_ty> for #set_ty #where_clause {
+                fn from_iter<#iter_param: IntoIterator<Item = #base_ty>>(iter: #iter_param) -> Self {
                     Self::from(iter)
                 }
             }
 
 
-            impl<'a> FromIterator<&'a #base_ty> for #name {
-                fn from_iter<T: IntoIterator<Item = &'a #base_ty>>(iter: T) -> Self {
-                    let items = iter.into_iter().fold(0, |acc, item| acc | base_to_value(item));
+            impl #ref_impl_generics FromIterator<&#item #base_ty> for #set_ty #where_clause {
+                fn from_iter<#iter_param: IntoIterator<Item = &#item #base_ty>>(iter: #iter_param) -> Self {
+                    let items = iter.into_iter().fold(bits::empty(), |acc, item| bits::or(acc, base_to_value(item)));
+
+                    Self { items, marker: PhantomData }
+                }
+            }
 
-                    Self { items }
+            impl #impl_generics Extend<#base_ty> for #set_ty #where_clause {
+                fn extend<#iter_param: IntoIterator<Item = #base_ty>>(&mut self, iter: #iter_param) {
+                    let items = iter.into_iter().fold(bits::empty(), |acc, item| bits::or(acc, base_to_value(&item)));
+                    self.items = bits::or(self.items, items);
                 }
             }
 
-            impl Extend<#base_ty> for #name {
-                fn extend<T: IntoIterator<Item = #base_ty>>(&mut self, iter: T) {
-                    self.items |= iter.into_iter().fold(0, |acc, item| acc | base_to_value(&item));
+            impl #ref_impl_generics Extend<&#item #base_ty> for #set_ty #where_clause {
+                fn extend<#iter_param: IntoIterator<Item = &#item #base_ty>>(&mut self, iter: #iter_param) {
+                    let items = iter.into_iter().fold(bits::empty(), |acc, item| bits::or(acc, base_to_value(item)));
+                    self.items = bits::or(self.items, items);
                 }
             }
 
-            impl<'a> Extend<&'a #base_ty> for #name {
-                fn extend<T: IntoIterator<Item = &'a #base_ty>>(&mut self, iter: T) {
-                    self.items |= iter.into_iter().fold(0, |acc, item| acc | base_to_value(item));
+            impl #impl_generics IntoIterator for #set_ty #clone_where {
+                type Item = #base_ty;
+                type IntoIter = #iter_ty;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    #iter { items: self.items, marker: PhantomData }
                 }
             }
 
-            impl IntoIterator for #name {
+            impl #ref_impl_generics IntoIterator for &#item #set_ty #clone_where {
                 type Item = #base_ty;
-                type IntoIter = #iter;
+                type IntoIter = #iter_ty;
 
                 fn into_iter(self) -> Self::IntoIter {
-                    #iter { items: self.items }
+                    self.iter()
                 }
             }
         }
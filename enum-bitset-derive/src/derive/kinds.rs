@@ -0,0 +1,225 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, Fields, Meta, Result, Token, parse_quote,
+    punctuated::Punctuated, spanned::Spanned,
+};
+
+use crate::derive::{config::EnumBitsetConfig, generate, serde::SerdeFormat};
+
+static SERDE_KINDS_MSG: &str = "The enum of kinds does not implement the serde traits, so its set can only be (de)serialized with `serde = \"repr\"`, `serde = \"map\"` or `serde = \"string\"`.";
+static INVALID_KINDS_MSG: &str =
+    "Invalid value for kinds. Expected `kinds`, or `kinds = Name` to choose the name of the generated enum.";
+
+
+/// Removes the `kinds` option from the `#[bitset(..)]` attributes of `input`, returning the name
+/// of the enum of kinds to generate, if it was present.
+pub fn extract_kinds(input: &mut DeriveInput) -> Result<Option<Ident>> {
+    let mut kinds = None;
+
+    for attr in input
+        .attrs
+        .iter_mut()
+        .filter(|attr| attr.path().is_ident("bitset"))
+    {
+        // Malformed attributes are reported by the regular derive
+        let Ok(metas) = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        else {
+            continue;
+        };
+        if !metas
+            .iter()
+            .any(|meta| meta.path().is_ident("kinds"))
+        {
+            continue;
+        }
+
+        let mut rest = Punctuated::<Meta, Token![,]>::new();
+
+        for meta in metas {
+            if !meta.path().is_ident("kinds") {
+                rest.push(meta);
+                continue;
+            }
+
+            kinds = Some(match &meta {
+                Meta::Path(path) => format_ident!("{}Kind", input.ident, span = path.span()),
+                Meta::NameValue(value) => match &value.value {
+                    Expr::Path(path) if path.qself.is_none() => path
+                        .path
+                        .get_ident()
+                        .cloned()
+                        .ok_or_else(|| Error::new(path.span(), INVALID_KINDS_MSG))?,
+                    other => return Err(Error::new(other.span(), INVALID_KINDS_MSG)),
+                },
+                Meta::List(list) => return Err(Error::new(list.span(), INVALID_KINDS_MSG)),
+            });
+        }
+
+        *attr = parse_quote!(#[bitset(#rest)]);
+    }
+
+    Ok(kinds)
+}
+
+
+/// Generates a fieldless enum named `kind`, with one variant per variant of the `input` enum, and
+/// its set through the regular derive. Then links both enums with `From` conversions, which the
+/// `contains` method of the set relies on, a `kind` method on the original enum, and
+/// `contains_kind_of` on the set.
+pub fn derive_kinds(input: DeriveInput, kind: Ident) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "EnumBitset can only be derived for enums",
+        ));
+    };
+
+    let base = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `bitset` attributes are handled by the derive of the kinds.
+    let kind_variants = data.variants.iter().map(|variant| {
+        let name = &variant.ident;
+        let attrs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("bitset"));
+        let discriminant = variant
+            .discriminant
+            .as_ref()
+            .map(|(eq, expr)| quote!(#eq #expr));
+        quote! { #(#attrs)* #name #discriminant }
+    });
+
+    let set_attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("bitset"));
+    // The enum of kinds does not implement the serde traits, so its set is not (de)serialized
+    // unless a format that does not need them is chosen.
+    let kind_input: DeriveInput = parse_quote! {
+        #[bitset(serde = false)]
+        #(#set_attrs)*
+        #vis enum #kind {
+            #(#kind_variants),*
+        }
+    };
+
+    let kind_branches = data.variants.iter().map(|variant| {
+        let name = &variant.ident;
+        let pattern = match variant.fields {
+            Fields::Unit => quote!(#base::#name),
+            Fields::Named(_) | Fields::Unnamed(_) => quote!(#base::#name { .. }),
+        };
+        quote! { #pattern => #kind::#name }
+    });
+
+    let kind_enum = strip_bitset_attrs(kind_input.clone());
+    let mut config: EnumBitsetConfig = kind_input.try_into()?;
+    let needs_serde_base = matches!(config.serde.format, SerdeFormat::List | SerdeFormat::Adaptive);
+    if cfg!(feature = "serde") && needs_serde_base && (config.serde.ser || config.serde.de) {
+        return Err(Error::new(kind.span(), SERDE_KINDS_MSG));
+    }
+    config.kinds_of = Some(base.clone());
+    let set_type = config.set_type.clone();
+    let set_tokens = generate(config);
+
+    let kind_doc = format!("The kind of a [`{base}`] value: its variant, without any fields.");
+    let method_doc = format!("Returns the [`{kind}`] of this value.");
+
+    Ok(quote! {
+        #[doc = #kind_doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #kind_enum
+
+        #set_tokens
+
+        impl #impl_generics #base #ty_generics #where_clause {
+            #[doc = #method_doc]
+            #[inline]
+            pub const fn kind(&self) -> #kind {
+                match self {
+                    #(#kind_branches),*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#base #ty_generics> for #kind #where_clause {
+            #[inline]
+            fn from(value: &#base #ty_generics) -> Self {
+                value.kind()
+            }
+        }
+
+        impl ::core::convert::From<&#kind> for #kind {
+            #[inline]
+            fn from(kind: &#kind) -> Self {
+                *kind
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#base #ty_generics> for #set_type #where_clause {
+            #[inline]
+            fn from(value: &#base #ty_generics) -> Self {
+                value.kind().as_bitset()
+            }
+        }
+
+        impl #set_type {
+            /// Returns `true` if the set contains the kind of `value`.
+            #[inline]
+            pub fn contains_kind_of #impl_generics (&self, value: &#base #ty_generics) -> bool #where_clause {
+                self.contains(value.kind())
+            }
+        }
+    })
+}
+
+
+/// Removes the `#[bitset(..)]` attributes of the enum and its variants, which are only meaningful
+/// to the derive.
+fn strip_bitset_attrs(mut input: DeriveInput) -> DeriveInput {
+    let is_bitset = |attr: &Attribute| attr.path().is_ident("bitset");
+
+    input.attrs.retain(|attr| !is_bitset(attr));
+    if let Data::Enum(data) = &mut input.data {
+        for variant in &mut data.variants {
+            variant.attrs.retain(|attr| !is_bitset(attr));
+        }
+    }
+
+    input
+}
+
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use super::*;
+
+    fn derive(input: &str) -> Result<TokenStream2> {
+        let mut input: DeriveInput = parse_str(input)?;
+        let kind = extract_kinds(&mut input)?.expect("kinds");
+        derive_kinds(input, kind)
+    }
+
+    #[test]
+    fn serde_formats() {
+        check_serde("#[bitset(kinds)] enum E { A(u8), B }", true);
+        check_serde("#[bitset(kinds, serde = \"repr\")] enum E { A(u8), B }", true);
+        check_serde("#[bitset(kinds, serde = \"map\", serde = \"ser\")] enum E { A(u8), B }", true);
+        check_serde("#[bitset(kinds, serde = false)] enum E { A(u8), B }", true);
+
+        let serde = cfg!(feature = "serde");
+        check_serde("#[bitset(kinds, serde = true)] enum E { A(u8), B }", !serde);
+        check_serde("#[bitset(kinds, serde = \"de\")] enum E { A(u8), B }", !serde);
+        check_serde("#[bitset(kinds, serde = \"adaptive\")] enum E { A(u8), B }", !serde);
+    }
+
+    fn check_serde(input: &str, ok: bool) {
+        assert_eq!(derive(input).is_ok(), ok, "{input}");
+    }
+}
@@ -1,13 +1,69 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 use syn::LitInt;
 
-use crate::derive::config::EnumBitsetConfig;
+use crate::derive::config::{BitsetVariant, EnumBitsetConfig, Repr};
 
 impl EnumBitsetConfig {
+    /// The mask of the set, as a literal of its representation type.
+    pub(crate) fn mask_tokens(&self) -> TokenStream2 {
+        self.mask_of(self.variants.iter())
+    }
+
+    /// Same as [`mask_tokens`](Self::mask_tokens), but only for the given `variants`.
+    pub(crate) fn mask_of<'a>(&self, variants: impl Iterator<Item = &'a BitsetVariant>) -> TokenStream2 {
+        let bits: Vec<usize> = variants.map(|variant| variant.bit).collect();
+
+        Self::mask_for(self.inner_type, &bits)
+    }
+
+    // Static for easier testing
+    pub(crate) fn mask_for(repr: Repr, bits: &[usize]) -> TokenStream2 {
+        let words = match repr {
+            Repr::Int(_) => {
+                let mask = Self::mask_bits(bits.iter().copied());
+                return quote!(#mask);
+            }
+            Repr::Words(words) => words,
+        };
+
+        let word_mask = |word: usize| {
+            let range = word * Repr::WORD_BITS..(word + 1) * Repr::WORD_BITS;
+            let word_bits = bits
+                .iter()
+                .filter(|bit| range.contains(bit))
+                .map(|bit| bit - range.start);
+
+            match word_bits.clone().count() {
+                0 => quote!(0),
+                Repr::WORD_BITS => quote!(u64::MAX),
+                _ => {
+                    let mask = Self::mask_bits(word_bits);
+                    quote!(#mask)
+                }
+            }
+        };
+
+        let items = (0..words).map(word_mask);
+        quote!([#(#items),*])
+    }
+
+    /// Binary literal with the given bit positions set to one.
     // Static for easier testing
-    pub(crate) fn mask(len: usize) -> LitInt {
-        let ones = "1".repeat(len);
-        let bin = format!("0b{ones}");
-        LitInt::new(&bin, proc_macro2::Span::call_site())
+    pub(crate) fn mask_bits(bits: impl Iterator<Item = usize> + Clone) -> LitInt {
+        let width = bits.clone().max().map_or(0, |bit| bit + 1);
+        let digits: String = (0..width)
+            .rev()
+            .map(|position| {
+                if bits.clone().any(|bit| bit == position) {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect();
+
+        LitInt::new(&format!("0b{digits}"), proc_macro2::Span::call_site())
     }
 }
 
@@ -17,20 +73,42 @@ mod tests {
 
     #[test]
     fn test_mask_3() {
-        let mask = EnumBitsetConfig::mask(3);
+        let mask = EnumBitsetConfig::mask_bits(0..3);
         assert_eq!(mask.to_string(), "0b111");
     }
 
     #[test]
     fn test_mask_10() {
-        let mask = EnumBitsetConfig::mask(10);
+        let mask = EnumBitsetConfig::mask_bits(0..10);
         assert_eq!(mask.to_string(), "0b1111111111");
     }
 
+    #[test]
+    fn test_mask_for_int() {
+        let mask = EnumBitsetConfig::mask_for(Repr::Int(16), &[0, 1, 2]);
+        assert_eq!(mask.to_string(), "0b111");
+    }
+
+    #[test]
+    fn test_mask_for_words() {
+        let bits: Vec<usize> = (0..130).collect();
+        let mask = EnumBitsetConfig::mask_for(Repr::Words(4), &bits);
+        assert_eq!(mask.to_string(), "[u64 :: MAX , u64 :: MAX , 0b11 , 0]");
+    }
+
+    #[test]
+    fn test_mask_for_sparse() {
+        let mask = EnumBitsetConfig::mask_for(Repr::Int(8), &[5, 0, 2]);
+        assert_eq!(mask.to_string(), "0b100101");
+
+        let mask = EnumBitsetConfig::mask_for(Repr::Words(2), &[1, 65]);
+        assert_eq!(mask.to_string(), "[0b10 , 0b10]");
+    }
+
     #[test]
     #[should_panic]
     fn test_mask_0() {
-        let mask = EnumBitsetConfig::mask(0);
+        let mask = EnumBitsetConfig::mask_bits(0..0);
         assert_eq!(mask.to_string(), "0b");
     }
 }
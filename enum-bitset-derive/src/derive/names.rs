@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::derive::config::EnumBitsetConfig;
+
+impl EnumBitsetConfig {
+    /// `NAMES`, `name` and `from_name`: the names of the variants, after applying `rename_all`
+    /// and `rename`.
+    pub(crate) fn impl_names(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let base_type = &self.base_type;
+        let base = self.base_ty();
+        let base_name = self.base_name();
+        let len = self.len();
+        let item_param = self.param("T");
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
+        let names = self.name_literals();
+
+        let name_branches = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = self.name_of(ident, variant.rename.as_ref());
+            quote! {#base_type::#ident => #name}
+        });
+
+        let skipped_name_branches = self.skipped.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = self.name_of(ident, variant.rename.as_ref());
+            quote! {#base_type::#ident { .. } => #name}
+        });
+
+        let from_name_branches = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = self.name_of(ident, variant.rename.as_ref());
+            quote! {#name => Option::Some(#base_type::#ident)}
+        });
+
+        let names_doc = format!(
+            "The names of the variants of [`{base_name}`] in the set, in the same order as \
+             [`VARIANTS`](Self::VARIANTS)."
+        );
+        let name_doc = format!(
+            "Returns the name of a variant of [`{base_name}`] (including the skipped ones). \
+             Accepts any borrow of [`{base_name}`]."
+        );
+        let from_name_doc = format!(
+            "Returns the variant of [`{base_name}`] with the given name, if it is part of the set."
+        );
+
+        quote! {
+            impl #impl_generics #set_ty #where_clause {
+                #[doc = #names_doc]
+                pub const NAMES: [&'static str; #len] = [#(#names),*];
+
+                #[doc = #name_doc]
+                #[inline]
+                pub fn name<#item_param: Borrow<#base>>(item: #item_param) -> &'static str {
+                    match item.borrow() {
+                        #(#name_branches,)*
+                        #(#skipped_name_branches,)*
+                    }
+                }
+
+                #[doc = #from_name_doc]
+                #[inline]
+                pub fn from_name(name: &str) -> Option<#base> {
+                    match name {
+                        #(#from_name_branches,)*
+                        _ => Option::None,
+                    }
+                }
+            }
+        }
+    }
+
+    /// The names of the variants, to list them in an array.
+    pub(crate) fn name_literals(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        self.variants.iter().map(|variant| {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+            quote! {#name}
+        })
+    }
+
+    /// `"Name" => bit` match branches, that map the name of every variant to its bit.
+    pub(crate) fn name_to_bit_branches(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        self.variants.iter().map(|variant| {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+            let bit = variant.bit as u32;
+            quote! {#name => bits::bit(#bit)}
+        })
+    }
+}
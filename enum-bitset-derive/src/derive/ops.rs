@@ -14,249 +14,250 @@ impl EnumBitsetConfig {
     }
 
     fn impl_ops_mandatory(&self) -> TokenStream2 {
-        let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let name = self.set_ty();
+        let base_ty = self.base_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
 
         quote! {
             use ::core::ops::*;
 
             #[allow(clippy::suspicious_arithmetic_impl)]
-            impl Add<#name> for #name {
+            impl #impl_generics Add<#name> for #name #where_clause {
                 type Output = Self;
 
                 fn add(self, rhs: Self) -> Self {
-                    Self { items: self.items | rhs.items}
+                    Self { items: bits::or(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
             #[allow(clippy::suspicious_arithmetic_impl)]
-            impl Add<&#name> for #name {
+            impl #impl_generics Add<&#name> for #name #where_clause {
                 type Output = Self;
 
                 fn add(self, rhs: &Self) -> Self {
-                    Self { items: self.items | rhs.items}
+                    Self { items: bits::or(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
             #[allow(clippy::suspicious_arithmetic_impl)]
-            impl Add<#base_ty> for #name {
+            impl #impl_generics Add<#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn add(mut self, rhs: #base_ty) -> Self {
-                    Self { items: self.items | base_to_value(&rhs) }
+                    Self { items: bits::or(self.items, base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
             #[allow(clippy::suspicious_arithmetic_impl)]
-            impl Add<&#base_ty> for #name {
+            impl #impl_generics Add<&#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn add(mut self, rhs: &#base_ty) -> Self {
-                    Self { items: self.items | base_to_value(rhs) }
+                    Self { items: bits::or(self.items, base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
             #[allow(clippy::suspicious_op_assign_impl)]
-            impl AddAssign<#name> for #name {
+            impl #impl_generics AddAssign<#name> for #name #where_clause {
                 fn add_assign(&mut self, rhs: Self) {
-                    self.items |= rhs.items;
+                    self.items = bits::or(self.items, rhs.items);
                 }
             }
 
             #[allow(clippy::suspicious_op_assign_impl)]
-            impl AddAssign<&#name> for #name {
+            impl #impl_generics AddAssign<&#name> for #name #where_clause {
                 fn add_assign(&mut self, rhs: &Self) {
-                    self.items |= rhs.items;
+                    self.items = bits::or(self.items, rhs.items);
                 }
             }
 
             #[allow(clippy::suspicious_op_assign_impl)]
-            impl AddAssign<#base_ty> for #name {
+            impl #impl_generics AddAssign<#base_ty> for #name #where_clause {
                 fn add_assign(&mut self, rhs: #base_ty) {
-                    self.items |= base_to_value(&rhs);
+                    self.items = bits::or(self.items, base_to_value(&rhs));
                 }
             }
 
             #[allow(clippy::suspicious_op_assign_impl)]
-            impl AddAssign<&#base_ty> for #name {
+            impl #impl_generics AddAssign<&#base_ty> for #name #where_clause {
                 fn add_assign(&mut self, rhs: &#base_ty) {
-                    self.items |= base_to_value(rhs);
+                    self.items = bits::or(self.items, base_to_value(rhs));
                 }
             }
 
-            impl Sub<#name> for #name {
+            impl #impl_generics Sub<#name> for #name #where_clause {
                 type Output = Self;
 
                 fn sub(self, rhs: Self) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    Self { items: self.items & !rhs.items }
+                    Self { items: bits::and_not(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl Sub<&#name> for #name {
+            impl #impl_generics Sub<&#name> for #name #where_clause {
                 type Output = Self;
 
                 fn sub(self, rhs: &Self) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    Self { items: self.items & !rhs.items }
+                    Self { items: bits::and_not(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl Sub<#base_ty> for #name {
+            impl #impl_generics Sub<#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn sub(self, rhs: #base_ty) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    Self { items: self.items & !base_to_value(&rhs) }
+                    Self { items: bits::and_not(self.items, base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
-            impl Sub<&#base_ty> for #name {
+            impl #impl_generics Sub<&#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn sub(self, rhs: &#base_ty) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    Self { items: self.items & !base_to_value(rhs) }
+                    Self { items: bits::and_not(self.items, base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl SubAssign<#name> for #name {
+            impl #impl_generics SubAssign<#name> for #name #where_clause {
                 fn sub_assign(&mut self, rhs: Self) {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    self.items &= !rhs.items;
+                    self.items = bits::and_not(self.items, rhs.items);
                 }
             }
 
-            impl SubAssign<&#name> for #name {
+            impl #impl_generics SubAssign<&#name> for #name #where_clause {
                 fn sub_assign(&mut self, rhs: &Self) {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    self.items &= !rhs.items;
+                    self.items = bits::and_not(self.items, rhs.items);
                 }
             }
 
-            impl SubAssign<#base_ty> for #name {
+            impl #impl_generics SubAssign<#base_ty> for #name #where_clause {
                 fn sub_assign(&mut self, rhs: #base_ty) {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    self.items &= !base_to_value(&rhs);
+                    self.items = bits::and_not(self.items, base_to_value(&rhs));
                 }
             }
 
-            impl SubAssign<&#base_ty> for #name {
+            impl #impl_generics SubAssign<&#base_ty> for #name #where_clause {
                 fn sub_assign(&mut self, rhs: &#base_ty) {
                     // No `Self::MASK &` necessary, since `self.items` must have invalid bits zeroed
-                    self.items &= !base_to_value(rhs);
+                    self.items = bits::and_not(self.items, base_to_value(rhs));
                 }
             }
 
-            impl BitAnd<#name> for #name {
+            impl #impl_generics BitAnd<#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitand(self, rhs: Self) -> Self {
-                    Self { items: self.items & rhs.items }
+                    Self { items: bits::and(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitAnd<&#name> for #name {
+            impl #impl_generics BitAnd<&#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitand(self, rhs: &Self) -> Self {
-                    Self { items: self.items & rhs.items }
+                    Self { items: bits::and(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitAndAssign<#name> for #name {
+            impl #impl_generics BitAndAssign<#name> for #name #where_clause {
                 fn bitand_assign(&mut self, rhs: Self) {
-                    self.items &= rhs.items;
+                    self.items = bits::and(self.items, rhs.items);
                 }
             }
 
-            impl BitAndAssign<&#name> for #name {
+            impl #impl_generics BitAndAssign<&#name> for #name #where_clause {
                 fn bitand_assign(&mut self, rhs: &Self) {
-                    self.items &= rhs.items;
+                    self.items = bits::and(self.items, rhs.items);
                 }
             }
 
-            impl BitOr<#name> for #name {
+            impl #impl_generics BitOr<#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitor(self, rhs: Self) -> Self {
-                    Self { items: self.items | rhs.items }
+                    Self { items: bits::or(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitOr<&#name> for #name {
+            impl #impl_generics BitOr<&#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitor(self, rhs: &Self) -> Self {
-                    Self { items: self.items | rhs.items }
+                    Self { items: bits::or(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitOr<#base_ty> for #name {
+            impl #impl_generics BitOr<#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn bitor(mut self, rhs: #base_ty) -> Self {
-                    Self { items: self.items | base_to_value(&rhs) }
+                    Self { items: bits::or(self.items, base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOr<&#base_ty> for #name {
+            impl #impl_generics BitOr<&#base_ty> for #name #where_clause {
                 type Output = Self;
 
                 fn bitor(mut self, rhs: &#base_ty) -> Self {
-                    Self { items: self.items | base_to_value(rhs) }
+                    Self { items: bits::or(self.items, base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOrAssign<#name> for #name {
+            impl #impl_generics BitOrAssign<#name> for #name #where_clause {
                 fn bitor_assign(&mut self, rhs: Self) {
-                    self.items |= rhs.items;
+                    self.items = bits::or(self.items, rhs.items);
                 }
             }
 
-            impl BitOrAssign<&#name> for #name {
+            impl #impl_generics BitOrAssign<&#name> for #name #where_clause {
                 fn bitor_assign(&mut self, rhs: &Self) {
-                    self.items |= rhs.items;
+                    self.items = bits::or(self.items, rhs.items);
                 }
             }
 
-            impl BitXor<#name> for #name {
+            impl #impl_generics BitXor<#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitxor(self, rhs: Self) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` and `rhs.items` must have invalid bits zeroed, 0^0 = 0.
-                    Self { items: self.items ^ rhs.items }
+                    Self { items: bits::xor(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitXor<&#name> for #name {
+            impl #impl_generics BitXor<&#name> for #name #where_clause {
                 type Output = Self;
 
                 fn bitxor(self, rhs: &Self) -> Self {
                     // No `Self::MASK &` necessary, since `self.items` and `rhs.items` must have invalid bits zeroed, 0^0 = 0.
-                    Self { items: self.items ^ rhs.items }
+                    Self { items: bits::xor(self.items, rhs.items), marker: PhantomData }
                 }
             }
 
-            impl BitXorAssign<#name> for #name {
+            impl #impl_generics BitXorAssign<#name> for #name #where_clause {
                 fn bitxor_assign(&mut self, rhs: Self) {
                     // No `Self::MASK &` necessary, since `self.items` and `rhs.items` must have invalid bits zeroed, 0^0 = 0.
-                    self.items ^= rhs.items;
+                    self.items = bits::xor(self.items, rhs.items);
                 }
             }
 
-            impl BitXorAssign<&#name> for #name {
+            impl #impl_generics BitXorAssign<&#name> for #name #where_clause {
                 fn bitxor_assign(&mut self, rhs: &Self) {
                     // No `Self::MASK &` necessary, since `self.items` and `rhs.items` must have invalid bits zeroed, 0^0 = 0.
-                    self.items ^= rhs.items;
+                    self.items = bits::xor(self.items, rhs.items);
                 }
             }
 
-            impl Not for #name {
+            impl #impl_generics Not for #name #where_clause {
                 type Output = Self;
 
                 fn not(self) -> Self {
-                    Self { items: (!self.items) & Self::MASK }
+                    Self { items: bits::and_not(Self::MASK, self.items), marker: PhantomData }
                 }
             }
         }
@@ -264,75 +265,77 @@ impl EnumBitsetConfig {
 
 
     fn impl_base_add(&self) -> TokenStream2 {
-        let name = &self.set_type;
-        let base_ty = &self.base_type;
+        let set_type = &self.set_type;
+        let name = self.set_ty();
+        let base_ty = self.base_ty();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
 
         quote! {
-            impl Add<#base_ty> for #base_ty {
+            impl #impl_generics Add<#base_ty> for #base_ty #where_clause {
                 type Output = #name;
 
                 #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, rhs: #base_ty) -> #name {
-                    #name { items: base_to_value(&self) | base_to_value(&rhs) }
+                    #set_type { items: bits::or(base_to_value(&self), base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
-            impl Add<&#base_ty> for #base_ty {
+            impl #impl_generics Add<&#base_ty> for #base_ty #where_clause {
                 type Output = #name;
 
                 #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, rhs: &#base_ty) -> #name {
-                    #name { items: base_to_value(&self) | base_to_value(rhs) }
+                    #set_type { items: bits::or(base_to_value(&self), base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl Add<&#base_ty> for &#base_ty {
+            impl #impl_generics Add<&#base_ty> for &#base_ty #where_clause {
                 type Output = #name;
 
                 #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, rhs: &#base_ty) -> #name {
-                    #name { items: base_to_value(self) | base_to_value(rhs) }
+                    #set_type { items: bits::or(base_to_value(self), base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl Add<#base_ty> for &#base_ty {
+            impl #impl_generics Add<#base_ty> for &#base_ty #where_clause {
                 type Output = #name;
 
                 #[allow(clippy::suspicious_arithmetic_impl)]
                 fn add(self, rhs: #base_ty) -> #name {
-                    #name { items: base_to_value(self) | base_to_value(&rhs) }
+                    #set_type { items: bits::or(base_to_value(self), base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOr<#base_ty> for #base_ty {
+            impl #impl_generics BitOr<#base_ty> for #base_ty #where_clause {
                 type Output = #name;
 
                 fn bitor(self, rhs: #base_ty) -> #name {
-                    #name { items: base_to_value(&self) | base_to_value(&rhs) }
+                    #set_type { items: bits::or(base_to_value(&self), base_to_value(&rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOr<&#base_ty> for #base_ty {
+            impl #impl_generics BitOr<&#base_ty> for #base_ty #where_clause {
                 type Output = #name;
 
                 fn bitor(self, rhs: &#base_ty) -> #name {
-                    #name { items: base_to_value(&self) | base_to_value(rhs) }
+                    #set_type { items: bits::or(base_to_value(&self), base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOr<&#base_ty> for &#base_ty {
+            impl #impl_generics BitOr<&#base_ty> for &#base_ty #where_clause {
                 type Output = #name;
 
                 fn bitor(self, rhs: &#base_ty) -> #name {
-                    #name { items: base_to_value(self) | base_to_value(rhs) }
+                    #set_type { items: bits::or(base_to_value(self), base_to_value(rhs)), marker: PhantomData }
                 }
             }
 
-            impl BitOr<#base_ty> for &#base_ty {
+            impl #impl_generics BitOr<#base_ty> for &#base_ty #where_clause {
                 type Output = #name;
 
                 fn bitor(self, rhs: #base_ty) -> #name {
-                    #name { items: base_to_value(self) | base_to_value(&rhs) }
+                    #set_type { items: bits::or(base_to_value(self), base_to_value(&rhs)), marker: PhantomData }
                 }
             }
         }
@@ -0,0 +1,176 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::derive::config::EnumBitsetConfig;
+
+impl EnumBitsetConfig {
+    /// Queries that rely on the order of the variants in the set: the order of their bits, which
+    /// is the declaration order unless bits are pinned out of order. The variants are created with
+    /// `bit_to_base` instead of cloned, so the queries are `const fn`, except the ones that go
+    /// through a trait (`Borrow` or `RangeBounds`), which cannot be called in constant contexts.
+    pub(crate) fn impl_ordered(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let base = self.base_ty();
+        let base_name = self.base_name();
+        let item_param = self.param("T");
+        let range_param = self.param("R");
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
+        let first_doc = "Returns the first variant in the set, the one with the lowest bit.";
+        let last_doc = "Returns the last variant in the set, the one with the highest bit.";
+        let pop_first_doc = "Removes the first variant in the set, and returns it.";
+        let pop_last_doc = "Removes the last variant in the set, and returns it.";
+        let next_after_doc = format!(
+            "Returns the first variant in the set that comes after `item`, which does not need to \
+             be in the set. Accepts any borrow of [`{base_name}`], and returns `None` for \
+             skipped variants. Unlike [`first`](Self::first), it is not a `const fn`, since \
+             borrowing `item` is a trait call."
+        );
+        let prev_before_doc = format!(
+            "Returns the last variant in the set that comes before `item`, which does not need to \
+             be in the set. Accepts any borrow of [`{base_name}`], and returns `None` for \
+             skipped variants. Unlike [`last`](Self::last), it is not a `const fn`, since \
+             borrowing `item` is a trait call."
+        );
+        let rank_doc = format!(
+            "Returns the number of variants in the set that come before `item`, which does not need \
+             to be in the set. For a variant in the set, this is its position in [`iter`](Self::iter), \
+             and the inverse of [`select`](Self::select). Accepts any borrow of [`{base_name}`], so \
+             it is not a `const fn`."
+        );
+        let rank_panics_doc = format!("Panics if `item` is a skipped variant of [`{base_name}`].");
+        let rank_panic = format!("skipped variants of `{base_name}` have no rank");
+        let select_doc = "Returns the `n`-th variant in the set, starting at 0, or `None` if the set \
+                          has `n` variants or fewer.";
+        let range_doc = format!(
+            "Returns the variants in the set that are within `range`, like `Run..` or \
+             `Setup..=Run`. The set is empty if a bound is a skipped variant of [`{base_name}`]. \
+             It is not a `const fn`, since the bounds are read through the `RangeBounds` trait."
+        );
+
+        quote! {
+            impl #impl_generics #set_ty #where_clause {
+                #[doc = #first_doc]
+                #[inline]
+                pub const fn first(&self) -> Option<#base> {
+                    if bits::is_empty(self.items) {
+                        return Option::None;
+                    }
+                    bit_to_base(bits::trailing_zeros(self.items))
+                }
+
+                #[doc = #last_doc]
+                #[inline]
+                pub const fn last(&self) -> Option<#base> {
+                    if bits::is_empty(self.items) {
+                        return Option::None;
+                    }
+                    bit_to_base(bits::highest(self.items))
+                }
+
+                #[doc = #pop_first_doc]
+                #[inline]
+                pub const fn pop_first(&mut self) -> Option<#base> {
+                    let first = self.first();
+                    self.items = bits::clear_lowest(self.items, 1);
+                    first
+                }
+
+                #[doc = #pop_last_doc]
+                #[inline]
+                pub const fn pop_last(&mut self) -> Option<#base> {
+                    let last = self.last();
+                    self.items = bits::clear_highest(self.items, 1);
+                    last
+                }
+
+                #[doc = #next_after_doc]
+                pub fn next_after<#item_param: Borrow<#base>>(&self, item: #item_param) -> Option<#base> {
+                    let value = base_to_value(item.borrow());
+                    if bits::is_empty(value) {
+                        return Option::None;
+                    }
+
+                    let below = bits::below(bits::trailing_zeros(value));
+                    let items = bits::and_not(self.items, bits::or(below, value));
+                    Self { items, marker: PhantomData }.first()
+                }
+
+                #[doc = #prev_before_doc]
+                pub fn prev_before<#item_param: Borrow<#base>>(&self, item: #item_param) -> Option<#base> {
+                    let value = base_to_value(item.borrow());
+                    if bits::is_empty(value) {
+                        return Option::None;
+                    }
+
+                    let items = bits::and(self.items, bits::below(bits::trailing_zeros(value)));
+                    Self { items, marker: PhantomData }.last()
+                }
+
+                #[doc = #rank_doc]
+                ///
+                /// # Panics
+                ///
+                #[doc = #rank_panics_doc]
+                pub fn rank<#item_param: Borrow<#base>>(&self, item: #item_param) -> usize {
+                    let value = base_to_value(item.borrow());
+                    if bits::is_empty(value) {
+                        ::core::panic!(#rank_panic);
+                    }
+
+                    let below = bits::below(bits::trailing_zeros(value));
+                    bits::count_ones(bits::and(self.items, below)) as usize
+                }
+
+                #[doc = #select_doc]
+                #[inline]
+                pub const fn select(&self, n: usize) -> Option<#base> {
+                    if n >= bits::count_ones(self.items) as usize {
+                        return Option::None;
+                    }
+                    bit_to_base(bits::trailing_zeros(bits::clear_lowest(self.items, n as u32)))
+                }
+
+                #[doc = #range_doc]
+                pub fn range<#range_param: ::core::ops::RangeBounds<#base>>(&self, range: #range_param) -> Self {
+                    use ::core::ops::Bound;
+
+                    let mut items = self.items;
+
+                    match range.start_bound() {
+                        Bound::Included(start) | Bound::Excluded(start) => {
+                            let value = base_to_value(start);
+                            if bits::is_empty(value) {
+                                return Self::empty();
+                            }
+
+                            items = bits::and_not(items, bits::below(bits::trailing_zeros(value)));
+                            if let Bound::Excluded(_) = range.start_bound() {
+                                items = bits::and_not(items, value);
+                            }
+                        }
+                        Bound::Unbounded => {}
+                    }
+
+                    match range.end_bound() {
+                        Bound::Included(end) | Bound::Excluded(end) => {
+                            let value = base_to_value(end);
+                            if bits::is_empty(value) {
+                                return Self::empty();
+                            }
+
+                            let mut keep = bits::below(bits::trailing_zeros(value));
+                            if let Bound::Included(_) = range.end_bound() {
+                                keep = bits::or(keep, value);
+                            }
+                            items = bits::and(items, keep);
+                        }
+                        Bound::Unbounded => {}
+                    }
+
+                    Self { items, marker: PhantomData }
+                }
+            }
+        }
+    }
+}
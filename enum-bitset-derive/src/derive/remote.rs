@@ -0,0 +1,167 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, DeriveInput, Error, Path, PathArguments, Result, Token, Variant, Visibility, braced,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+};
+
+use crate::derive::{config::EnumBitsetConfig, generate};
+
+static GENERIC_REMOTE_MSG: &str = "remote! does not support generic enums";
+
+
+/// Input of the `remote!` macro:
+///
+/// ```text
+/// #[bitset(..)]
+/// pub LevelSet for log::Level { Error, Warn, Info, Debug, Trace }
+/// ```
+pub struct RemoteInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    set_type: Ident,
+    base_type: Path,
+    variants: Punctuated<Variant, Token![,]>,
+}
+
+impl Parse for RemoteInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let set_type = input.parse()?;
+        input.parse::<Token![for]>()?;
+        let base_type: Path = input.parse()?;
+
+        if let Some(segment) = base_type
+            .segments
+            .iter()
+            .find(|segment| !matches!(segment.arguments, PathArguments::None))
+        {
+            return Err(Error::new(segment.arguments.span(), GENERIC_REMOTE_MSG));
+        }
+
+        let content;
+        braced!(content in input);
+        let variants = content.parse_terminated(Variant::parse, Token![,])?;
+
+        Ok(Self { attrs, vis, set_type, base_type, variants })
+    }
+}
+
+
+/// Generates the set for an enum declared elsewhere, from the list of its variants.
+///
+/// The variants are declared again in a hidden mirror enum, which goes through the
+/// `__RemoteEnumBitset` derive. This way the compiler removes the variants with an inactive
+/// `#[cfg(..)]` attribute before the set is generated, exactly as for the regular derive.
+pub fn remote_enum_bitset(input: RemoteInput) -> Result<TokenStream2> {
+    let RemoteInput { attrs, vis, set_type, base_type, variants } = input;
+
+    let Some(base_name) = base_type.segments.last().map(|segment| &segment.ident) else {
+        return Err(Error::new(base_type.span(), "Expected the path to an enum"));
+    };
+
+    // Checked here as well, so that errors point to the macro call instead of the mirror enum
+    let input: DeriveInput = parse_quote! {
+        #(#attrs)*
+        #[bitset(name = #set_type)]
+        #vis enum #base_name {
+            #variants
+        }
+    };
+    let config: EnumBitsetConfig = input.try_into()?;
+    let my_crate = &config.my_crate;
+    let mirror = format_ident!("__{set_type}Remote");
+
+    Ok(quote! {
+        #[derive(#my_crate::__RemoteEnumBitset)]
+        #(#attrs)*
+        #[bitset(name = #set_type)]
+        #[remote_bitset(#base_type)]
+        #[doc(hidden)]
+        #[allow(dead_code, non_camel_case_types)]
+        #vis enum #mirror {
+            #variants
+        }
+    })
+}
+
+
+/// Generates the set for the mirror enum emitted by `remote!`, whose `#[remote_bitset(..)]`
+/// attribute holds the path to the actual base enum. Nothing is implemented on the base enum
+/// itself (`as_bitset`, `BitsetBase` and the operators between variants), since the orphan rules
+/// would not allow it for enums of other crates. The match in `base_to_value`, which has no
+/// wildcard branch, ensures that the list of variants is exhaustive.
+pub fn derive_remote(mut input: DeriveInput) -> Result<TokenStream2> {
+    let Some(position) = input
+        .attrs
+        .iter()
+        .position(|attr| attr.path().is_ident("remote_bitset"))
+    else {
+        return Err(Error::new(input.span(), "Expected a `remote_bitset` attribute"));
+    };
+    let base_type: Path = input.attrs.remove(position).parse_args()?;
+
+    if let Some(base_name) = base_type.segments.last() {
+        input.ident = base_name.ident.clone();
+    }
+
+    let mut config: EnumBitsetConfig = input.try_into()?;
+    config.base_type = module_relative(base_type);
+    config.base_add = false;
+    config.remote = true;
+
+    Ok(generate(config))
+}
+
+
+/// The generated code lives in a child module that imports everything from the module where the
+/// macro is invoked, so paths relative to the current module must go one level up.
+fn module_relative(path: Path) -> Path {
+    let Some(first) = path.segments.first() else {
+        return path;
+    };
+
+    if path.leading_colon.is_some() {
+        path
+    } else if first.ident == "self" {
+        let rest = path.segments.iter().skip(1);
+        parse_quote!(super #(::#rest)*)
+    } else if first.ident == "super" {
+        parse_quote!(super::#path)
+    } else {
+        path
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse_str;
+
+    use super::*;
+
+    fn relative(path: &str) -> String {
+        let path = module_relative(parse_str(path).unwrap());
+        quote!(#path).to_string().replace(' ', "")
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(relative("log::Level"), "log::Level");
+        assert_eq!(relative("::std::net::Shutdown"), "::std::net::Shutdown");
+        assert_eq!(relative("crate::proto::Kind"), "crate::proto::Kind");
+        assert_eq!(relative("self::proto::Kind"), "super::proto::Kind");
+        assert_eq!(relative("super::Kind"), "super::super::Kind");
+    }
+
+    #[test]
+    fn generic_paths_are_rejected() {
+        assert!(parse_str::<RemoteInput>("KindSet for Kind<u8> { A }").is_err());
+        assert!(parse_str::<RemoteInput>("pub KindSet for proto::Kind { A, B }").is_ok());
+    }
+}
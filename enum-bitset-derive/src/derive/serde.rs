@@ -1,12 +1,60 @@
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{LitStr, Path, parse_str};
+use syn::{Error, Lifetime, LitStr, Path, Result, parse_str};
 
 use crate::derive::config::EnumBitsetConfig;
 
+mod adaptive;
+mod map;
+mod repr;
+mod string;
+
+/// The shape of the serialized set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerdeFormat {
+    /// A sequence with the variants in the set (the default).
+    List,
+    /// The integer representation of the set, from `serde = "repr"`.
+    Repr,
+    /// A map from the name of every variant to whether it is in the set, from `serde = "map"`.
+    Map,
+    /// The names of the variants in the set, joined by `sep` in a single string, from
+    /// `serde = "string"`.
+    String,
+    /// The list for human-readable formats, and the integer representation for the other ones,
+    /// from `serde = "adaptive"`.
+    Adaptive,
+}
+
+static SEP_WITHOUT_STRING_MSG: &str = "`sep` can only be used with `serde = \"string\"`";
+static EMPTY_SEP_MSG: &str = "`sep` cannot be empty";
+static ADAPTIVE_UNKNOWN_MSG: &str = "`serde_unknown = \"ignore\"` cannot be used with `serde = \"adaptive\"`, since binary formats use the integer representation, where unknown variants cannot be told apart from invalid data";
+
+/// What to do with unknown variant names when deserializing, from `serde_unknown = ".."`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownPolicy {
+    Error,
+    Ignore,
+}
+
+/// What to do with variants that appear more than once when deserializing, from
+/// `serde_duplicates = ".."`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    Ignore,
+    Error,
+}
+
 pub struct SerdeConfig {
     pub ser: bool,
     pub de: bool,
+    pub format: SerdeFormat,
+    pub unknown: UnknownPolicy,
+    /// Where `serde_unknown` was given, to report it when it does not apply to the format.
+    pub unknown_span: Option<Span>,
+    pub duplicates: DuplicatePolicy,
+    /// Separator of the names with `serde = "string"`, from `sep = ".."`.
+    pub sep: Option<LitStr>,
     pub serde_crate: Path,
 }
 
@@ -15,16 +63,53 @@ impl Default for SerdeConfig {
         SerdeConfig {
             ser: true,
             de: true,
+            format: SerdeFormat::List,
+            unknown: UnknownPolicy::Error,
+            unknown_span: None,
+            duplicates: DuplicatePolicy::Ignore,
+            sep: None,
             serde_crate: parse_str("::serde").expect("::serde is a valid path"),
         }
     }
 }
 
+impl SerdeConfig {
+    /// The separator of the names with `serde = "string"`, `","` unless given.
+    pub fn sep(&self) -> String {
+        self.sep
+            .as_ref()
+            .map_or_else(|| ",".into(), LitStr::value)
+    }
+
+    /// Checks the options that only apply to some formats.
+    pub fn check(&self) -> Result<()> {
+        if let (UnknownPolicy::Ignore, SerdeFormat::Adaptive, Some(span)) =
+            (self.unknown, self.format, self.unknown_span)
+        {
+            return Err(Error::new(span, ADAPTIVE_UNKNOWN_MSG));
+        }
+
+        match &self.sep {
+            Some(sep) if self.format != SerdeFormat::String => {
+                Err(Error::new(sep.span(), SEP_WITHOUT_STRING_MSG))
+            }
+            Some(sep) if sep.value().is_empty() => Err(Error::new(sep.span(), EMPTY_SEP_MSG)),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl EnumBitsetConfig {
     #[cfg(feature = "serde")]
     pub fn impl_serde(&self) -> TokenStream2 {
-        self.impl_serde_list()
+        match self.serde.format {
+            SerdeFormat::List => self.impl_serde_list(),
+            SerdeFormat::Repr => self.impl_serde_repr(),
+            SerdeFormat::Map => self.impl_serde_map(),
+            SerdeFormat::String => self.impl_serde_string(),
+            SerdeFormat::Adaptive => self.impl_serde_adaptive(),
+        }
     }
 
     #[cfg(not(feature = "serde"))]
@@ -49,29 +134,26 @@ impl EnumBitsetConfig {
 
     #[allow(dead_code)]
     fn impl_serde_list_ser(&self) -> TokenStream2 {
-        let name = &self.set_type;
+        let name = self.set_ty();
         let serde = &self.serde.serde_crate;
+        let (impl_generics, _, _) = self.generics.split_for_impl();
+        let where_clause = self.where_clause_with(quote!(Clone + Serialize));
+        let serializer = self.param("S");
+
+        let check = self.check_base_serialize();
+        let serialize = self.serialize_list();
 
         quote! {
             use #serde::{Serialize, Serializer, ser::{SerializeSeq}};
 
-            const _: fn() = || {
-                fn base_enum_for_bitsets_must_be_serializable<T: Serialize>() {}
-                base_enum_for_bitsets_must_be_serializable::<#name>();
-            };
+            #check
 
-            impl Serialize for #name {
-                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            impl #impl_generics Serialize for #name #where_clause {
+                fn serialize<#serializer>(&self, serializer: #serializer) -> Result<#serializer::Ok, #serializer::Error>
                 where
-                    S: Serializer,
+                    #serializer: Serializer,
                 {
-                    let mut seq = serializer.serialize_seq(Some(self.len()))?;
-
-                    for variant in self.iter() {
-                        seq.serialize_element(&variant)?;
-                    }
-
-                    seq.end()
+                    #serialize
                 }
             }
         }
@@ -79,50 +161,213 @@ impl EnumBitsetConfig {
 
     #[allow(dead_code)]
     fn impl_serde_list_de(&self) -> TokenStream2 {
-        let name = &self.set_type;
-        let base = &self.base_type;
+        let set_ty = self.set_ty();
         let serde = &self.serde.serde_crate;
 
-        let expect = LitStr::new(&format!("a list {name} of variants"), name.span());
+        let de = self.lifetime("de");
+        let de_generics = self.generics_with_lifetime(&de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let where_clause = self.where_clause_with(self.list_de_bound(&de));
+        let deserializer = self.param("D");
+
+        let check = self.check_base_deserialize();
+        let deserialize = self.deserialize_list(&de);
 
         quote! {
             use #serde::{Deserialize, Deserializer, de::{SeqAccess, Visitor}};
 
-            const _: fn() = || {
-                fn base_enum_for_bitsets_must_be_deserializable<T: for<'de> Deserialize<'de>>() {}
-                base_enum_for_bitsets_must_be_deserializable::<#name>();
-            };
+            #check
 
-            impl<'de> Deserialize<'de> for #name {
-                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            impl #de_impl_generics Deserialize<#de> for #set_ty #where_clause {
+                fn deserialize<#deserializer>(deserializer: #deserializer) -> Result<Self, #deserializer::Error>
                 where
-                    D: Deserializer<'de>,
+                    #deserializer: Deserializer<#de>,
                 {
-                    struct VisitList;
+                    #deserialize
+                }
+            }
+        }
+    }
 
-                    impl<'de> Visitor<'de> for VisitList {
-                        type Value = #name;
+    /// A compile time check with a readable error when the base enum is not `Serialize`. Generic
+    /// base enums rely on the bounds of the impl instead.
+    #[allow(dead_code)]
+    fn check_base_serialize(&self) -> Option<TokenStream2> {
+        let name = self.set_ty();
+        let serde = &self.serde.serde_crate;
 
-                        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                            formatter.write_str(#expect)
-                        }
+        (!self.is_generic()).then(|| {
+            quote! {
+                const _: fn() = || {
+                    fn base_enum_for_bitsets_must_be_serializable<T: #serde::Serialize>() {}
+                    base_enum_for_bitsets_must_be_serializable::<#name>();
+                };
+            }
+        })
+    }
+
+    /// Same as [`check_base_serialize`](Self::check_base_serialize), for `Deserialize`.
+    #[allow(dead_code)]
+    fn check_base_deserialize(&self) -> Option<TokenStream2> {
+        let name = &self.set_type;
+        let serde = &self.serde.serde_crate;
+
+        (!self.is_generic()).then(|| {
+            quote! {
+                const _: fn() = || {
+                    fn base_enum_for_bitsets_must_be_deserializable<T: for<'de> #serde::Deserialize<'de>>() {}
+                    base_enum_for_bitsets_must_be_deserializable::<#name>();
+                };
+            }
+        })
+    }
+
+    /// Body of `serialize` that writes the variants in `self` as a sequence to `serializer`.
+    #[allow(dead_code)]
+    fn serialize_list(&self) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+
+        quote! {
+            use #serde::ser::SerializeSeq;
+
+            let mut seq = #serde::Serializer::serialize_seq(serializer, Some(self.len()))?;
 
-                        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-                        where
-                            A: serde::de::SeqAccess<'de>,
-                        {
-                            let mut result = #name::new();
-                            while let Some(variant) = seq.next_element::<#base>()? {
-                                result.insert(variant);
+            for variant in self.iter() {
+                seq.serialize_element(&variant)?;
+            }
+
+            seq.end()
+        }
+    }
+
+    /// The bound of the base enum to deserialize a list. Skipping unknown variants needs to
+    /// deserialize them from a string that does not outlive the element.
+    #[allow(dead_code)]
+    pub(crate) fn list_de_bound(&self, de: &Lifetime) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+
+        match self.serde.unknown {
+            UnknownPolicy::Error => quote!(#serde::Deserialize<#de>),
+            UnknownPolicy::Ignore => quote!(#serde::de::DeserializeOwned),
+        }
+    }
+
+    /// `return Err(..)` for a variant that appears twice in `set_name`, named by `name`.
+    #[allow(dead_code)]
+    pub(crate) fn duplicate_error(&self, error: TokenStream2, name: TokenStream2) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+        let message = format!("duplicate variant `{{}}` in {}", self.set_type);
+
+        quote! {
+            return Err(<#error as #serde::de::Error>::custom(format_args!(#message, #name)))
+        }
+    }
+
+    /// Body of `deserialize` that reads a sequence of variants from `deserializer`.
+    #[allow(dead_code)]
+    fn deserialize_list(&self, de: &Lifetime) -> TokenStream2 {
+        let name = &self.set_type;
+        let set_ty = self.set_ty();
+        let base = self.base_ty();
+        let serde = &self.serde.serde_crate;
+
+        let de_generics = self.generics_with_lifetime(de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let (_, visitor_ty_generics, _) = self.generics.split_for_impl();
+        let visitor_generics = &self.generics.params;
+        let visitor_where = &self.generics.where_clause;
+        let where_clause = self.where_clause_with(self.list_de_bound(de));
+        let access = self.param("A");
+        let error = self.param("E");
+
+        let expect = LitStr::new(&format!("a list {name} of variants"), name.span());
+
+        let duplicate = match self.serde.duplicates {
+            DuplicatePolicy::Ignore => None,
+            DuplicatePolicy::Error => {
+                let error = self.duplicate_error(quote!(#access::Error), quote!(<#set_ty>::name(&variant)));
+                Some(quote! {
+                    if result.contains(&variant) {
+                        #error;
+                    }
+                })
+            }
+        };
+
+        // Unknown variants are read as strings, and kept only if the base enum accepts them
+        let (element, skip_unknown) = match self.serde.unknown {
+            UnknownPolicy::Error => (quote!(#base), None),
+            UnknownPolicy::Ignore => (
+                quote!(MaybeVariant #visitor_ty_generics),
+                Some(quote! {
+                    let MaybeVariant(Some(variant)) = variant else {
+                        continue;
+                    };
+                }),
+            ),
+        };
+
+        let maybe_variant = (self.serde.unknown == UnknownPolicy::Ignore).then(|| {
+            quote! {
+                struct MaybeVariant<#visitor_generics>(Option<#base>) #visitor_where;
+
+                impl #de_impl_generics #serde::Deserialize<#de> for MaybeVariant #visitor_ty_generics #where_clause {
+                    fn deserialize<D: #serde::Deserializer<#de>>(deserializer: D) -> Result<Self, D::Error> {
+                        struct VisitName<#visitor_generics>(PhantomData<fn() -> #base>) #visitor_where;
+
+                        impl #de_impl_generics #serde::de::Visitor<#de> for VisitName #visitor_ty_generics #where_clause {
+                            type Value = MaybeVariant #visitor_ty_generics;
+
+                            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                                formatter.write_str("the name of a variant")
+                            }
+
+                            fn visit_str<#error: #serde::de::Error>(self, name: &str) -> Result<Self::Value, #error> {
+                                let name: #serde::de::value::StrDeserializer<#error> =
+                                    #serde::de::IntoDeserializer::into_deserializer(name);
+                                Ok(MaybeVariant(<#base as #serde::Deserialize>::deserialize(name).ok()))
                             }
+                        }
 
-                            Ok(result)
+                        if #serde::Deserializer::is_human_readable(&deserializer) {
+                            deserializer.deserialize_str(VisitName(PhantomData))
+                        } else {
+                            // Binary formats cannot skip a value they do not know
+                            <#base as #serde::Deserialize>::deserialize(deserializer).map(|variant| MaybeVariant(Some(variant)))
                         }
                     }
+                }
+            }
+        });
+
+        quote! {
+            #maybe_variant
+
+            struct VisitList<#visitor_generics>(PhantomData<fn() -> #set_ty>) #visitor_where;
+
+            impl #de_impl_generics #serde::de::Visitor<#de> for VisitList #visitor_ty_generics #where_clause {
+                type Value = #set_ty;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str(#expect)
+                }
+
+                fn visit_seq<#access>(self, mut seq: #access) -> Result<Self::Value, #access::Error>
+                where
+                    #access: #serde::de::SeqAccess<#de>,
+                {
+                    let mut result = <#set_ty>::new();
+                    while let Some(variant) = seq.next_element::<#element>()? {
+                        #skip_unknown
+                        #duplicate
+                        result.insert(variant);
+                    }
 
-                    deserializer.deserialize_seq(VisitList)
+                    Ok(result)
                 }
             }
+
+            #serde::Deserializer::deserialize_seq(deserializer, VisitList(PhantomData))
         }
     }
 }
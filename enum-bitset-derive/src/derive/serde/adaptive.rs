@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::derive::config::EnumBitsetConfig;
+
+#[allow(dead_code)]
+impl EnumBitsetConfig {
+    /// `serde = "adaptive"`: the set is serialized as a list of variants when the format is
+    /// human-readable (like JSON), and as its integer representation otherwise (like bincode).
+    pub(crate) fn impl_serde_adaptive(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let serde = &self.serde.serde_crate;
+        let (impl_generics, _, _) = self.generics.split_for_impl();
+        let de = self.lifetime("de");
+        let de_generics = self.generics_with_lifetime(&de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let ser_where = self.where_clause_with(quote!(Clone + #serde::Serialize));
+        let de_where = self.where_clause_with(self.list_de_bound(&de));
+        let serializer = self.param("S");
+        let deserializer = self.param("D");
+
+        let ser_check = self.check_base_serialize();
+        let de_check = self.check_base_deserialize();
+        let serialize_list = self.serialize_list();
+        let serialize_repr = self.serialize_repr();
+        let deserialize_list = self.deserialize_list(&de);
+        let deserialize_repr = self.deserialize_repr(&deserializer, &de);
+
+        let ser_impl = self.serde.ser.then(|| {
+            quote! {
+                #ser_check
+
+                impl #impl_generics #serde::Serialize for #set_ty #ser_where {
+                    fn serialize<#serializer>(&self, serializer: #serializer) -> Result<#serializer::Ok, #serializer::Error>
+                    where
+                        #serializer: #serde::Serializer,
+                    {
+                        if #serde::Serializer::is_human_readable(&serializer) {
+                            #serialize_list
+                        } else {
+                            #serialize_repr
+                        }
+                    }
+                }
+            }
+        });
+        let de_impl = self.serde.de.then(|| {
+            quote! {
+                #de_check
+
+                impl #de_impl_generics #serde::Deserialize<#de> for #set_ty #de_where {
+                    fn deserialize<#deserializer>(deserializer: #deserializer) -> Result<Self, #deserializer::Error>
+                    where
+                        #deserializer: #serde::Deserializer<#de>,
+                    {
+                        if #serde::Deserializer::is_human_readable(&deserializer) {
+                            #deserialize_list
+                        } else {
+                            #deserialize_repr
+                        }
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #ser_impl
+            #de_impl
+        }
+    }
+}
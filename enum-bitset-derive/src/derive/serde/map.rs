@@ -0,0 +1,211 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::Lifetime;
+
+use crate::derive::{
+    config::EnumBitsetConfig,
+    serde::{DuplicatePolicy, UnknownPolicy},
+};
+
+#[allow(dead_code)]
+impl EnumBitsetConfig {
+    /// `serde = "map"`: the set is serialized as a map from the name of every variant to a
+    /// boolean, which is `true` if the variant is in the set.
+    pub(crate) fn impl_serde_map(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let serde = &self.serde.serde_crate;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let de = self.lifetime("de");
+        let de_generics = self.generics_with_lifetime(&de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let serializer = self.param("S");
+        let deserializer = self.param("D");
+
+        let serialize = self.serialize_map();
+        let deserialize = self.deserialize_map(&de);
+
+        let ser_impl = self.serde.ser.then(|| {
+            quote! {
+                impl #impl_generics #serde::Serialize for #set_ty #where_clause {
+                    fn serialize<#serializer>(&self, serializer: #serializer) -> Result<#serializer::Ok, #serializer::Error>
+                    where
+                        #serializer: #serde::Serializer,
+                    {
+                        #serialize
+                    }
+                }
+            }
+        });
+        let de_impl = self.serde.de.then(|| {
+            quote! {
+                impl #de_impl_generics #serde::Deserialize<#de> for #set_ty #where_clause {
+                    fn deserialize<#deserializer>(deserializer: #deserializer) -> Result<Self, #deserializer::Error>
+                    where
+                        #deserializer: #serde::Deserializer<#de>,
+                    {
+                        #deserialize
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #ser_impl
+            #de_impl
+        }
+    }
+
+    /// Body of `serialize` that writes every variant of `self`, with its flag, to `serializer`.
+    fn serialize_map(&self) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+        let len = self.len();
+
+        let entries = self.variants.iter().map(|variant| {
+            let name = self.name_of(&variant.ident, variant.rename.as_ref());
+            let bit = variant.bit as u32;
+
+            quote! {
+                map.serialize_entry(#name, &!bits::is_empty(bits::and(self.items, bits::bit(#bit))))?;
+            }
+        });
+
+        quote! {
+            use #serde::ser::SerializeMap;
+
+            let mut map = #serde::Serializer::serialize_map(serializer, Some(#len))?;
+            #(#entries)*
+            map.end()
+        }
+    }
+
+    /// Body of `deserialize` that reads a map of flags from `deserializer`. Absent variants are
+    /// not in the set.
+    fn deserialize_map(&self, de: &Lifetime) -> TokenStream2 {
+        let name = &self.set_type;
+        let serde = &self.serde.serde_crate;
+        let inner_ty = &self.inner_type;
+        let access = self.param("A");
+        let error = self.param("E");
+
+        let expected = format!("a map from the variants of {name} to booleans");
+        let key = self.deserialize_name_key(&error);
+
+        // Variants are in `seen` once their key has been read, even if their value is `false`
+        let duplicate = match self.serde.duplicates {
+            DuplicatePolicy::Ignore => None,
+            DuplicatePolicy::Error => {
+                let error = self.duplicate_error(quote!(#access::Error), quote!(name));
+                let names = self.variants.iter().map(|variant| {
+                    let name = self.name_of(&variant.ident, variant.rename.as_ref());
+                    let bit = variant.bit as u32;
+                    quote! {#bit => #name}
+                });
+
+                Some(quote! {
+                    if !bits::is_empty(bits::and(seen, bit)) {
+                        let name = match bits::trailing_zeros(bit) {
+                            #(#names,)*
+                            _ => unreachable!(),
+                        };
+                        #error;
+                    }
+                    seen = bits::or(seen, bit);
+                })
+            }
+        };
+        let seen = duplicate.is_some().then(|| {
+            quote! { let mut seen: #inner_ty = bits::empty(); }
+        });
+
+        let unknown = match self.serde.unknown {
+            // Reported by the key itself
+            UnknownPolicy::Error => quote! { unreachable!() },
+            UnknownPolicy::Ignore => quote! {
+                map.next_value::<#serde::de::IgnoredAny>()?;
+            },
+        };
+
+        quote! {
+            #key
+
+            struct VisitMap;
+
+            impl<#de> #serde::de::Visitor<#de> for VisitMap {
+                type Value = #inner_ty;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str(#expected)
+                }
+
+                fn visit_map<#access>(self, mut map: #access) -> Result<Self::Value, #access::Error>
+                where
+                    #access: #serde::de::MapAccess<#de>,
+                {
+                    let mut items: #inner_ty = bits::empty();
+                    #seen
+
+                    while let Some(NameKey(bit)) = map.next_key::<NameKey>()? {
+                        match bit {
+                            Some(bit) => {
+                                #duplicate
+                                if map.next_value::<bool>()? {
+                                    items = bits::or(items, bit);
+                                }
+                            }
+                            None => { #unknown }
+                        }
+                    }
+
+                    Ok(items)
+                }
+            }
+
+            let items = #serde::Deserializer::deserialize_map(deserializer, VisitMap)?;
+            Ok(Self { items, marker: PhantomData })
+        }
+    }
+
+    /// A `NameKey` type, that deserializes the name of a variant into its bit, or `None` if the
+    /// name is unknown and unknown names are ignored.
+    pub(crate) fn deserialize_name_key(&self, error: &Ident) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+        let inner_ty = &self.inner_type;
+
+        let names = self.name_literals();
+        let branches = self.name_to_bit_branches();
+
+        let unknown = match self.serde.unknown {
+            UnknownPolicy::Error => quote! {
+                return Err(#serde::de::Error::unknown_variant(name, &[#(#names),*]))
+            },
+            UnknownPolicy::Ignore => quote! { return Ok(NameKey(None)) },
+        };
+
+        quote! {
+            struct NameKey(Option<#inner_ty>);
+
+            impl<'de> #serde::Deserialize<'de> for NameKey {
+                fn deserialize<D: #serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct VisitName;
+
+                    impl<'de> #serde::de::Visitor<'de> for VisitName {
+                        type Value = NameKey;
+
+                        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                            formatter.write_str("the name of a variant")
+                        }
+
+                        fn visit_str<#error: #serde::de::Error>(self, name: &str) -> Result<NameKey, #error> {
+                            Ok(NameKey(Some(match name {
+                                #(#branches,)*
+                                _ => #unknown,
+                            })))
+                        }
+                    }
+
+                    deserializer.deserialize_str(VisitName)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::Lifetime;
+
+use crate::derive::config::{EnumBitsetConfig, Repr};
+
+#[allow(dead_code)]
+impl EnumBitsetConfig {
+    /// `serde = "repr"`: the set is serialized as its integer representation (or a tuple of
+    /// words), so the base enum does not need to implement the serde traits.
+    pub(crate) fn impl_serde_repr(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let serde = &self.serde.serde_crate;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let de = self.lifetime("de");
+        let de_generics = self.generics_with_lifetime(&de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let serializer = self.param("S");
+        let deserializer = self.param("D");
+
+        let serialize = self.serialize_repr();
+        let deserialize = self.deserialize_repr(&deserializer, &de);
+
+        let ser_impl = self.serde.ser.then(|| {
+            quote! {
+                impl #impl_generics #serde::Serialize for #set_ty #where_clause {
+                    fn serialize<#serializer>(&self, serializer: #serializer) -> Result<#serializer::Ok, #serializer::Error>
+                    where
+                        #serializer: #serde::Serializer,
+                    {
+                        #serialize
+                    }
+                }
+            }
+        });
+        let de_impl = self.serde.de.then(|| {
+            quote! {
+                impl #de_impl_generics #serde::Deserialize<#de> for #set_ty #where_clause {
+                    fn deserialize<#deserializer>(deserializer: #deserializer) -> Result<Self, #deserializer::Error>
+                    where
+                        #deserializer: #serde::Deserializer<#de>,
+                    {
+                        #deserialize
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #ser_impl
+            #de_impl
+        }
+    }
+
+    /// Body of `serialize` that writes `self` as its representation to `serializer`.
+    pub(crate) fn serialize_repr(&self) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+
+        match self.inner_type {
+            Repr::Int(_) => quote! {
+                #serde::Serialize::serialize(&self.items, serializer)
+            },
+            Repr::Words(words) => quote! {
+                use #serde::ser::SerializeTuple;
+
+                let mut tuple = #serde::Serializer::serialize_tuple(serializer, #words)?;
+                for word in &self.items {
+                    tuple.serialize_element(word)?;
+                }
+                tuple.end()
+            },
+        }
+    }
+
+    /// Body of `deserialize` that reads the representation of the set from `deserializer`,
+    /// rejecting the ones with reserved bits set.
+    pub(crate) fn deserialize_repr(&self, deserializer: &Ident, de: &Lifetime) -> TokenStream2 {
+        let name = &self.set_type;
+        let serde = &self.serde.serde_crate;
+        let inner_ty = &self.inner_type;
+        let expected = format!("a valid {name} representation");
+
+        let read = match self.inner_type {
+            Repr::Int(_) => quote! {
+                <#inner_ty as #serde::Deserialize>::deserialize(deserializer)?
+            },
+            Repr::Words(words) => {
+                let access = self.param("A");
+                let expected_words = format!("a tuple of {words} u64 words");
+
+                quote! {{
+                    struct VisitWords;
+
+                    impl<#de> #serde::de::Visitor<#de> for VisitWords {
+                        type Value = #inner_ty;
+
+                        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                            formatter.write_str(#expected_words)
+                        }
+
+                        fn visit_seq<#access>(self, mut seq: #access) -> Result<Self::Value, #access::Error>
+                        where
+                            #access: #serde::de::SeqAccess<#de>,
+                        {
+                            let mut words = [0u64; #words];
+                            for (index, word) in words.iter_mut().enumerate() {
+                                *word = seq
+                                    .next_element()?
+                                    .ok_or_else(|| #serde::de::Error::invalid_length(index, &self))?;
+                            }
+                            Ok(words)
+                        }
+                    }
+
+                    #serde::Deserializer::deserialize_tuple(deserializer, #words, VisitWords)?
+                }}
+            }
+        };
+
+        quote! {
+            let repr: #inner_ty = #read;
+
+            Self::from_repr(repr).ok_or_else(|| {
+                <#deserializer::Error as #serde::de::Error>::invalid_value(
+                    #serde::de::Unexpected::Other("reserved bits set"),
+                    &#expected,
+                )
+            })
+        }
+    }
+}
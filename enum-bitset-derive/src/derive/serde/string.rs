@@ -0,0 +1,141 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Lifetime;
+
+use crate::derive::{
+    config::EnumBitsetConfig,
+    serde::{DuplicatePolicy, UnknownPolicy},
+};
+
+#[allow(dead_code)]
+impl EnumBitsetConfig {
+    /// `serde = "string"`: the set is serialized as a single string, with the names of the
+    /// variants in the set separated by `sep`.
+    pub(crate) fn impl_serde_string(&self) -> TokenStream2 {
+        let set_ty = self.set_ty();
+        let serde = &self.serde.serde_crate;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let de = self.lifetime("de");
+        let de_generics = self.generics_with_lifetime(&de);
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let serializer = self.param("S");
+        let deserializer = self.param("D");
+
+        let serialize = self.serialize_string();
+        let deserialize = self.deserialize_string(&de);
+
+        let ser_impl = self.serde.ser.then(|| {
+            quote! {
+                impl #impl_generics #serde::Serialize for #set_ty #where_clause {
+                    fn serialize<#serializer>(&self, serializer: #serializer) -> Result<#serializer::Ok, #serializer::Error>
+                    where
+                        #serializer: #serde::Serializer,
+                    {
+                        #serialize
+                    }
+                }
+            }
+        });
+        let de_impl = self.serde.de.then(|| {
+            quote! {
+                impl #de_impl_generics #serde::Deserialize<#de> for #set_ty #where_clause {
+                    fn deserialize<#deserializer>(deserializer: #deserializer) -> Result<Self, #deserializer::Error>
+                    where
+                        #deserializer: #serde::Deserializer<#de>,
+                    {
+                        #deserialize
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #ser_impl
+            #de_impl
+        }
+    }
+
+    /// Body of `serialize` that writes the names in `self` to `serializer`, as one string.
+    fn serialize_string(&self) -> TokenStream2 {
+        let serde = &self.serde.serde_crate;
+        let inner_ty = &self.inner_type;
+        let write_names = self.write_names(&quote!(self.0), &self.serde.sep());
+
+        // `collect_str` does not need an allocator
+        quote! {
+            struct Joined(#inner_ty);
+
+            impl Display for Joined {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    #write_names
+                }
+            }
+
+            #serde::Serializer::collect_str(serializer, &Joined(self.items))
+        }
+    }
+
+    /// Body of `deserialize` that parses the names in a string from `deserializer`. Whitespace
+    /// around the names is ignored, and an empty string is the empty set.
+    fn deserialize_string(&self, de: &Lifetime) -> TokenStream2 {
+        let name = &self.set_type;
+        let serde = &self.serde.serde_crate;
+        let my_crate = &self.my_crate;
+        let inner_ty = &self.inner_type;
+        let error = self.param("E");
+        let sep = self.serde.sep();
+
+        let expected = format!("a string with the variants of {name}, separated by {sep:?}");
+        let names = self.name_literals();
+        let branches = self.name_to_bit_branches();
+
+        let unknown = match self.serde.unknown {
+            UnknownPolicy::Error => quote! {
+                return Err(#serde::de::Error::unknown_variant(token, &[#(#names),*]))
+            },
+            UnknownPolicy::Ignore => quote! { continue },
+        };
+
+        let duplicate = match self.serde.duplicates {
+            DuplicatePolicy::Ignore => None,
+            DuplicatePolicy::Error => {
+                let error = self.duplicate_error(quote!(#error), quote!(token));
+                Some(quote! {
+                    if !bits::is_empty(bits::and(items, bit)) {
+                        #error;
+                    }
+                })
+            }
+        };
+
+        quote! {
+            struct VisitString;
+
+            impl<#de> #serde::de::Visitor<#de> for VisitString {
+                type Value = #inner_ty;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str(#expected)
+                }
+
+                fn visit_str<#error: #serde::de::Error>(self, s: &str) -> Result<Self::Value, #error> {
+                    let mut items: #inner_ty = bits::empty();
+
+                    for token in #my_crate::display_impl::Tokens::new(s, #sep) {
+                        let bit = match token {
+                            #(#branches,)*
+                            _ => #unknown,
+                        };
+                        #duplicate
+                        items = bits::or(items, bit);
+                    }
+
+                    Ok(items)
+                }
+            }
+
+            let items = #serde::Deserializer::deserialize_str(deserializer, VisitString)?;
+            Ok(Self { items, marker: PhantomData })
+        }
+    }
+}
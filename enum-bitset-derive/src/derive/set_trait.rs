@@ -0,0 +1,94 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+use crate::derive::config::EnumBitsetConfig;
+
+impl EnumBitsetConfig {
+    pub fn impl_set_trait(&self) -> TokenStream2 {
+        let name = self.set_ty();
+        let base_ty = self.base_ty();
+        let inner_ty = &self.inner_type;
+        let iter = self.iter_ty();
+        let my_crate = &self.my_crate;
+        let (impl_generics, _, _) = self.generics.split_for_impl();
+        let where_clause = self.where_clause_with(quote!(Clone + 'static));
+        let item_param = self.param("T");
+
+        quote! {
+            impl #impl_generics #my_crate::EnumBitset for #name #where_clause {
+                type Base = #base_ty;
+                type Repr = #inner_ty;
+                type Iter = #iter;
+
+                const MASK: #inner_ty = <#name>::MASK;
+                const VARIANTS: &'static [#base_ty] = &<#name>::VARIANTS;
+                const NAMES: &'static [&'static str] = &<#name>::NAMES;
+
+                #[inline]
+                fn empty() -> Self {
+                    <#name>::empty()
+                }
+
+                #[inline]
+                fn all() -> Self {
+                    <#name>::all()
+                }
+
+                #[inline]
+                fn is_empty(&self) -> bool {
+                    <#name>::is_empty(self)
+                }
+
+                #[inline]
+                fn len(&self) -> usize {
+                    <#name>::len(self)
+                }
+
+                #[inline]
+                fn insert<#item_param: Borrow<#base_ty>>(&mut self, item: #item_param) {
+                    <#name>::insert(self, item)
+                }
+
+                #[inline]
+                fn remove<#item_param: Borrow<#base_ty>>(&mut self, item: #item_param) {
+                    <#name>::remove(self, item)
+                }
+
+                #[inline]
+                fn contains<#item_param: Borrow<#base_ty>>(&self, item: #item_param) -> bool {
+                    <#name>::contains(self, item.borrow())
+                }
+
+                #[inline]
+                fn union(&self, other: &Self) -> Self {
+                    <#name>::union(self, other)
+                }
+
+                #[inline]
+                fn iter(&self) -> #iter {
+                    <#name>::iter(self)
+                }
+
+                #[inline]
+                fn name<#item_param: Borrow<#base_ty>>(item: #item_param) -> &'static str {
+                    <#name>::name(item)
+                }
+
+                #[inline]
+                fn from_name(name: &str) -> Option<#base_ty> {
+                    <#name>::from_name(name)
+                }
+
+                #[inline]
+                fn to_repr(&self) -> #inner_ty {
+                    <#name>::to_repr(self)
+                }
+
+                #[inline]
+                fn from_repr(repr: #inner_ty) -> Option<Self> {
+                    <#name>::from_repr(repr)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+
+use crate::derive::config::EnumBitsetConfig;
+
+impl EnumBitsetConfig {
+    /// Names of the iterators over subsets, supersets and combinations of the set.
+    pub(crate) fn subset_iter_types(&self) -> [Ident; 3] {
+        let name = &self.set_type;
+        [
+            format_ident!("{name}Subsets"),
+            format_ident!("{name}Supersets"),
+            format_ident!("{name}Combinations"),
+        ]
+    }
+
+    /// Iterators that enumerate sets instead of variants. They walk the inner representation
+    /// with the usual "next subset" tricks: adding 1 (or the lowest bit) to the bits of the
+    /// current subset, with the bits outside the mask set to 1 so that the carry skips them.
+    pub(crate) fn impl_subsets(&self) -> TokenStream2 {
+        let name = &self.set_type;
+        let set_ty = self.set_ty();
+        let inner_ty = &self.inner_type;
+        let inner_vis = &self.inner_vis;
+        let [subsets, supersets, combinations] = self.subset_iter_types();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let struct_generics = &self.generics.params;
+        let struct_where = &self.generics.where_clause;
+        let derive_clone = (!self.is_generic()).then(|| quote!(#[derive(Clone)]));
+
+        let subsets_doc = format!(
+            "Iterator returned by the [`subsets`]({name}::subsets) method. Every subset is \
+             yielded once, from the empty set to the full one."
+        );
+        let supersets_doc = format!(
+            "Iterator returned by the [`supersets`]({name}::supersets) method. Every superset is \
+             yielded once, from the set itself to [`all`]({name}::all)."
+        );
+        let combinations_doc = format!(
+            "Iterator returned by the [`combinations`]({name}::combinations) method. Every subset \
+             with the requested number of variants is yielded once, starting with the lowest bits."
+        );
+
+        let method_subsets_doc = format!(
+            "Returns an iterator over all the subsets of the set, as [`{name}`] instances. There \
+             are `2^len` of them, including the empty set and the set itself."
+        );
+        let method_supersets_doc = format!(
+            "Returns an iterator over all the supersets of the set, as [`{name}`] instances. There \
+             are `2^(COUNT - len)` of them, including the set itself and [`all`](Self::all)."
+        );
+        let method_combinations_doc = format!(
+            "Returns an iterator over the subsets of the set that contain exactly `k` variants, as \
+             [`{name}`] instances. It is empty if `k` is greater than the length of the set."
+        );
+
+        // Subsets and supersets enumerate the subsets of `free`, and add `fixed` to them.
+        let fixed_free = |iter: &Ident, doc: &str| {
+            quote! {
+                #[doc = #doc]
+                #derive_clone
+                #inner_vis struct #iter <#struct_generics> #struct_where {
+                    fixed: #inner_ty,
+                    free: #inner_ty,
+                    next: Option<#inner_ty>,
+                    marker: PhantomData<fn() -> #set_ty>,
+                }
+
+                impl #impl_generics Iterator for #iter #ty_generics #where_clause {
+                    type Item = #set_ty;
+
+                    fn next(&mut self) -> Option<#set_ty> {
+                        let current = self.next?;
+
+                        let carried = bits::or(current, bits::not(self.free));
+                        let following = bits::and(bits::wrapping_add(carried, bits::bit(0)), self.free);
+                        self.next = if bits::is_empty(following) {
+                            Option::None
+                        } else {
+                            Option::Some(following)
+                        };
+
+                        Option::Some(#name { items: bits::or(self.fixed, current), marker: PhantomData })
+                    }
+                }
+
+                impl #impl_generics ::core::iter::FusedIterator for #iter #ty_generics #where_clause {}
+            }
+        };
+        let subsets_struct = fixed_free(&subsets, &subsets_doc);
+        let supersets_struct = fixed_free(&supersets, &supersets_doc);
+
+        quote! {
+            #subsets_struct
+            #supersets_struct
+
+            #[doc = #combinations_doc]
+            #derive_clone
+            #inner_vis struct #combinations <#struct_generics> #struct_where {
+                mask: #inner_ty,
+                next: Option<#inner_ty>,
+                marker: PhantomData<fn() -> #set_ty>,
+            }
+
+            impl #impl_generics Iterator for #combinations #ty_generics #where_clause {
+                type Item = #set_ty;
+
+                fn next(&mut self) -> Option<#set_ty> {
+                    let current = self.next?;
+
+                    self.next = if bits::is_empty(current) {
+                        Option::None
+                    } else {
+                        // Moves the lowest run of bits up by one position within the mask, then
+                        // packs the rest of the run at the bottom of the mask.
+                        let lowest = bits::bit(bits::trailing_zeros(current));
+                        let carried = bits::or(current, bits::not(self.mask));
+                        let moved = bits::and(bits::wrapping_add(carried, lowest), self.mask);
+
+                        if bits::is_empty(moved) {
+                            Option::None
+                        } else {
+                            let packed = bits::count_ones(current) - bits::count_ones(moved);
+                            let bottom = bits::and_not(self.mask, bits::clear_lowest(self.mask, packed));
+                            Option::Some(bits::or(moved, bottom))
+                        }
+                    };
+
+                    Option::Some(#name { items: current, marker: PhantomData })
+                }
+            }
+
+            impl #impl_generics ::core::iter::FusedIterator for #combinations #ty_generics #where_clause {}
+
+            impl #impl_generics #set_ty #where_clause {
+                #[doc = #method_subsets_doc]
+                #[inline]
+                pub const fn subsets(&self) -> #subsets #ty_generics {
+                    #subsets {
+                        fixed: bits::empty(),
+                        free: self.items,
+                        next: Option::Some(bits::empty()),
+                        marker: PhantomData,
+                    }
+                }
+
+                #[doc = #method_supersets_doc]
+                #[inline]
+                pub const fn supersets(&self) -> #supersets #ty_generics {
+                    #supersets {
+                        fixed: self.items,
+                        free: bits::and_not(Self::MASK, self.items),
+                        next: Option::Some(bits::empty()),
+                        marker: PhantomData,
+                    }
+                }
+
+                #[doc = #method_combinations_doc]
+                #[inline]
+                pub const fn combinations(&self, k: usize) -> #combinations #ty_generics {
+                    let next = if k > bits::count_ones(self.items) as usize {
+                        Option::None
+                    } else {
+                        let lowest = bits::clear_lowest(self.items, k as u32);
+                        Option::Some(bits::and_not(self.items, lowest))
+                    };
+
+                    #combinations { mask: self.items, next, marker: PhantomData }
+                }
+            }
+        }
+    }
+}
@@ -18,3 +18,24 @@ pub fn enum_bitset(input: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+#[proc_macro]
+pub fn remote(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as derive::RemoteInput);
+
+    match derive::remote_enum_bitset(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro_derive(__RemoteEnumBitset, attributes(bitset, remote_bitset))]
+pub fn remote_enum_bitset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive::derive_remote(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,419 @@
+//! Bit manipulation primitives used by the code generated by the [`EnumBitset`](derive@crate::EnumBitset)
+//! macro.
+//!
+//! Every backing representation has its own module exposing the same set of `const` functions, so
+//! the generated code can be written once and just import the right module as `bits`. The
+//! primitive unsigned integers map directly to the native operators, while [`words`] implements
+//! them for arrays of `u64` (used by enums with more than 128 variants).
+//!
+//! This module is an implementation detail, and is not covered by semver guarantees.
+
+macro_rules! int_bits {
+    ($($ty:ident),*) => {$(
+        #[doc = concat!("Bit operations for sets backed by a single `", stringify!($ty), "`.")]
+        pub mod $ty {
+            type Repr = ::core::primitive::$ty;
+
+            #[inline(always)]
+            pub const fn empty() -> Repr {
+                0
+            }
+
+            #[inline(always)]
+            pub const fn bit(index: u32) -> Repr {
+                1 << index
+            }
+
+            #[inline(always)]
+            pub const fn or(a: Repr, b: Repr) -> Repr {
+                a | b
+            }
+
+            #[inline(always)]
+            pub const fn and(a: Repr, b: Repr) -> Repr {
+                a & b
+            }
+
+            #[inline(always)]
+            pub const fn and_not(a: Repr, b: Repr) -> Repr {
+                a & !b
+            }
+
+            #[inline(always)]
+            pub const fn xor(a: Repr, b: Repr) -> Repr {
+                a ^ b
+            }
+
+            #[inline(always)]
+            pub const fn not(a: Repr) -> Repr {
+                !a
+            }
+
+            #[inline(always)]
+            pub const fn eq(a: Repr, b: Repr) -> bool {
+                a == b
+            }
+
+            #[inline(always)]
+            pub const fn is_empty(a: Repr) -> bool {
+                a == 0
+            }
+
+            #[inline(always)]
+            pub const fn count_ones(a: Repr) -> u32 {
+                a.count_ones()
+            }
+
+            #[inline(always)]
+            pub const fn trailing_zeros(a: Repr) -> u32 {
+                a.trailing_zeros()
+            }
+
+            #[inline(always)]
+            pub const fn leading_zeros(a: Repr) -> u32 {
+                a.leading_zeros()
+            }
+
+            /// Index of the most significant bit set to 1. `a` must not be empty.
+            #[inline(always)]
+            pub const fn highest(a: Repr) -> u32 {
+                Repr::BITS - 1 - a.leading_zeros()
+            }
+
+            /// Mask with the bits below `index` set to 1. `index` may be the width of the type.
+            #[inline(always)]
+            pub const fn below(index: u32) -> Repr {
+                match index {
+                    Repr::BITS => !0,
+                    _ => (1 << index) - 1,
+                }
+            }
+
+            /// Clears the `n` least significant bits set to 1.
+            #[inline]
+            pub const fn clear_lowest(mut a: Repr, mut n: u32) -> Repr {
+                while n > 0 && a != 0 {
+                    a &= a - 1;
+                    n -= 1;
+                }
+                a
+            }
+
+            /// Clears the `n` most significant bits set to 1.
+            #[inline]
+            pub const fn clear_highest(mut a: Repr, mut n: u32) -> Repr {
+                while n > 0 && a != 0 {
+                    a &= !(1 << highest(a));
+                    n -= 1;
+                }
+                a
+            }
+
+            #[inline(always)]
+            pub const fn wrapping_add(a: Repr, b: Repr) -> Repr {
+                a.wrapping_add(b)
+            }
+        }
+    )*};
+}
+
+int_bits!(u8, u16, u32, u64, u128);
+
+
+/// Bit operations for sets backed by an array of `u64` words.
+///
+/// The word at index 0 holds the 64 least significant bits, the word at index 1 the next 64, and
+/// so on.
+pub mod words {
+    const WORD_BITS: u32 = u64::BITS;
+
+    #[inline(always)]
+    pub const fn empty<const N: usize>() -> [u64; N] {
+        [0; N]
+    }
+
+    #[inline(always)]
+    pub const fn bit<const N: usize>(index: u32) -> [u64; N] {
+        let mut result = [0; N];
+        result[(index / WORD_BITS) as usize] = 1 << (index % WORD_BITS);
+        result
+    }
+
+    #[inline]
+    pub const fn or<const N: usize>(mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        let mut index = 0;
+        while index < N {
+            a[index] |= b[index];
+            index += 1;
+        }
+        a
+    }
+
+    #[inline]
+    pub const fn and<const N: usize>(mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        let mut index = 0;
+        while index < N {
+            a[index] &= b[index];
+            index += 1;
+        }
+        a
+    }
+
+    #[inline]
+    pub const fn and_not<const N: usize>(mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        let mut index = 0;
+        while index < N {
+            a[index] &= !b[index];
+            index += 1;
+        }
+        a
+    }
+
+    #[inline]
+    pub const fn xor<const N: usize>(mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        let mut index = 0;
+        while index < N {
+            a[index] ^= b[index];
+            index += 1;
+        }
+        a
+    }
+
+    #[inline]
+    pub const fn not<const N: usize>(mut a: [u64; N]) -> [u64; N] {
+        let mut index = 0;
+        while index < N {
+            a[index] = !a[index];
+            index += 1;
+        }
+        a
+    }
+
+    #[inline]
+    pub const fn eq<const N: usize>(a: [u64; N], b: [u64; N]) -> bool {
+        let mut index = 0;
+        while index < N {
+            if a[index] != b[index] {
+                return false;
+            }
+            index += 1;
+        }
+        true
+    }
+
+    #[inline]
+    pub const fn is_empty<const N: usize>(a: [u64; N]) -> bool {
+        eq(a, [0; N])
+    }
+
+    #[inline]
+    pub const fn count_ones<const N: usize>(a: [u64; N]) -> u32 {
+        let mut count = 0;
+        let mut index = 0;
+        while index < N {
+            count += a[index].count_ones();
+            index += 1;
+        }
+        count
+    }
+
+    #[inline]
+    pub const fn trailing_zeros<const N: usize>(a: [u64; N]) -> u32 {
+        let mut index = 0;
+        while index < N {
+            if a[index] != 0 {
+                return index as u32 * WORD_BITS + a[index].trailing_zeros();
+            }
+            index += 1;
+        }
+        N as u32 * WORD_BITS
+    }
+
+    #[inline]
+    pub const fn leading_zeros<const N: usize>(a: [u64; N]) -> u32 {
+        let mut index = N;
+        while index > 0 {
+            index -= 1;
+            if a[index] != 0 {
+                return (N - 1 - index) as u32 * WORD_BITS + a[index].leading_zeros();
+            }
+        }
+        N as u32 * WORD_BITS
+    }
+
+    /// Index of the most significant bit set to 1. `a` must not be empty.
+    #[inline]
+    pub const fn highest<const N: usize>(a: [u64; N]) -> u32 {
+        N as u32 * WORD_BITS - 1 - leading_zeros(a)
+    }
+
+    /// Mask with the bits below `index` set to 1. `index` may be the width of the array.
+    #[inline]
+    pub const fn below<const N: usize>(index: u32) -> [u64; N] {
+        let mut result = [0; N];
+        let mut word = 0;
+        while word < N {
+            let start = word as u32 * WORD_BITS;
+            if index >= start + WORD_BITS {
+                result[word] = u64::MAX;
+            } else if index > start {
+                result[word] = (1 << (index - start)) - 1;
+            }
+            word += 1;
+        }
+        result
+    }
+
+    /// Clears the `n` least significant bits set to 1. Whole words are skipped when they have at
+    /// most `n` bits set.
+    #[inline]
+    pub const fn clear_lowest<const N: usize>(mut a: [u64; N], mut n: u32) -> [u64; N] {
+        let mut index = 0;
+        while index < N && n > 0 {
+            let ones = a[index].count_ones();
+            if ones <= n {
+                a[index] = 0;
+                n -= ones;
+            } else {
+                while n > 0 {
+                    a[index] &= a[index] - 1;
+                    n -= 1;
+                }
+            }
+            index += 1;
+        }
+        a
+    }
+
+    /// Clears the `n` most significant bits set to 1. Whole words are skipped when they have at
+    /// most `n` bits set.
+    #[inline]
+    pub const fn clear_highest<const N: usize>(mut a: [u64; N], mut n: u32) -> [u64; N] {
+        let mut index = N;
+        while index > 0 && n > 0 {
+            index -= 1;
+            let ones = a[index].count_ones();
+            if ones <= n {
+                a[index] = 0;
+                n -= ones;
+            } else {
+                while n > 0 {
+                    a[index] &= !(1 << (u64::BITS - 1 - a[index].leading_zeros()));
+                    n -= 1;
+                }
+            }
+        }
+        a
+    }
+
+    /// Adds `a` and `b` as a single number, discarding the carry out of the last word.
+    #[inline]
+    pub const fn wrapping_add<const N: usize>(mut a: [u64; N], b: [u64; N]) -> [u64; N] {
+        let mut carry = false;
+        let mut index = 0;
+        while index < N {
+            let (sum, first) = a[index].overflowing_add(b[index]);
+            let (sum, second) = sum.overflowing_add(carry as u64);
+            a[index] = sum;
+            carry = first || second;
+            index += 1;
+        }
+        a
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::words;
+
+    #[test]
+    fn words_bit() {
+        assert_eq!(words::bit::<3>(0), [1, 0, 0]);
+        assert_eq!(words::bit::<3>(63), [1 << 63, 0, 0]);
+        assert_eq!(words::bit::<3>(64), [0, 1, 0]);
+        assert_eq!(words::bit::<3>(130), [0, 0, 4]);
+    }
+
+    #[test]
+    fn words_binary_ops() {
+        let a = [0b1100, 1, u64::MAX];
+        let b = [0b1010, 0, 1];
+
+        assert_eq!(words::or(a, b), [0b1110, 1, u64::MAX]);
+        assert_eq!(words::and(a, b), [0b1000, 0, 1]);
+        assert_eq!(words::and_not(a, b), [0b0100, 1, u64::MAX - 1]);
+        assert_eq!(words::xor(a, b), [0b0110, 1, u64::MAX - 1]);
+        assert_eq!(words::not(b), [!0b1010, u64::MAX, u64::MAX - 1]);
+    }
+
+    #[test]
+    fn words_queries() {
+        assert!(words::is_empty::<2>(words::empty()));
+        assert!(!words::is_empty([0, 1]));
+        assert!(words::eq([3, 4], [3, 4]));
+        assert!(!words::eq([3, 4], [3, 5]));
+
+        assert_eq!(words::count_ones([0b111, u64::MAX]), 67);
+        assert_eq!(words::trailing_zeros([0, 0b100]), 66);
+        assert_eq!(words::trailing_zeros([0b1000, 1]), 3);
+        assert_eq!(words::trailing_zeros::<2>([0, 0]), 128);
+    }
+
+    #[test]
+    fn words_highest() {
+        assert_eq!(words::leading_zeros([0b1000, 1]), 63);
+        assert_eq!(words::leading_zeros([0b1000, 0]), 124);
+        assert_eq!(words::leading_zeros::<2>([0, 0]), 128);
+        assert_eq!(words::highest([0b1000, 0]), 3);
+        assert_eq!(words::highest([1, 1 << 63]), 127);
+    }
+
+    #[test]
+    fn below() {
+        assert_eq!(super::u8::below(0), 0);
+        assert_eq!(super::u8::below(3), 0b111);
+        assert_eq!(super::u8::below(8), u8::MAX);
+
+        assert_eq!(words::below::<2>(0), [0, 0]);
+        assert_eq!(words::below::<2>(5), [0b11111, 0]);
+        assert_eq!(words::below::<2>(64), [u64::MAX, 0]);
+        assert_eq!(words::below::<2>(66), [u64::MAX, 0b11]);
+        assert_eq!(words::below::<2>(128), [u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    fn clear_lowest() {
+        assert_eq!(super::u8::clear_lowest(0b1011_0110, 0), 0b1011_0110);
+        assert_eq!(super::u8::clear_lowest(0b1011_0110, 3), 0b1010_0000);
+        assert_eq!(super::u8::clear_lowest(0b1011_0110, 9), 0);
+
+        assert_eq!(words::clear_lowest([0b111, 0b1010], 2), [0b100, 0b1010]);
+        assert_eq!(words::clear_lowest([0b111, 0b1010], 4), [0, 0b1000]);
+        assert_eq!(words::clear_lowest([0b111, 0b1010], 5), [0, 0]);
+        assert_eq!(words::clear_lowest([0, u64::MAX], 64), [0, 0]);
+    }
+
+    #[test]
+    fn clear_highest() {
+        assert_eq!(super::u8::clear_highest(0b1011_0110, 0), 0b1011_0110);
+        assert_eq!(super::u8::clear_highest(0b1011_0110, 3), 0b0000_0110);
+        assert_eq!(super::u8::clear_highest(0b1011_0110, 9), 0);
+
+        assert_eq!(words::clear_highest([0b1010, 0b111], 2), [0b1010, 0b001]);
+        assert_eq!(words::clear_highest([0b1010, 0b111], 4), [0b0010, 0]);
+        assert_eq!(words::clear_highest([0b1010, 0b111], 5), [0, 0]);
+        assert_eq!(words::clear_highest([u64::MAX, 0], 64), [0, 0]);
+    }
+
+    #[test]
+    fn wrapping_add() {
+        assert_eq!(super::u8::wrapping_add(0b0110, 0b0010), 0b1000);
+        assert_eq!(super::u8::wrapping_add(u8::MAX, 1), 0);
+
+        assert_eq!(words::wrapping_add([3, 0], [4, 0]), [7, 0]);
+        assert_eq!(words::wrapping_add([u64::MAX, 0b10], [1, 0]), [0, 0b11]);
+        assert_eq!(words::wrapping_add([u64::MAX, u64::MAX], [1, 0]), [0, 0]);
+        assert_eq!(words::wrapping_add([u64::MAX, 5], [u64::MAX, 0]), [u64::MAX - 1, 6]);
+    }
+}
@@ -0,0 +1,143 @@
+use core::{
+    error::Error,
+    fmt,
+    fmt::{Debug, Display, Formatter},
+};
+
+/// Maximum number of bytes of the offending token kept by a [`ParseSetError`].
+const MAX_TOKEN_LEN: usize = 32;
+
+
+/// Error returned when parsing a set from a string fails, because one of the tokens is not the
+/// name of a variant.
+///
+/// Since this crate does not allocate, the error keeps at most the first 32 bytes of the token.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ParseSetError {
+    token: [u8; MAX_TOKEN_LEN],
+    len: u8,
+    truncated: bool,
+}
+
+impl ParseSetError {
+    #[doc(hidden)]
+    pub fn unknown(token: &str) -> Self {
+        let mut len = token.len().min(MAX_TOKEN_LEN);
+        while !token.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut buffer = [0; MAX_TOKEN_LEN];
+        buffer[..len].copy_from_slice(&token.as_bytes()[..len]);
+
+        Self {
+            token: buffer,
+            len: len as u8,
+            truncated: len < token.len(),
+        }
+    }
+
+    /// The token that is not the name of a variant (possibly truncated, see
+    /// [`is_truncated`](Self::is_truncated)). It is empty if the string contains an empty token,
+    /// like in `"A,,B"`.
+    pub fn token(&self) -> &str {
+        core::str::from_utf8(&self.token[..self.len as usize]).unwrap_or_default()
+    }
+
+    /// Returns `true` if [`token`](Self::token) only contains the beginning of the token, because
+    /// it was too long.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl Display for ParseSetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.token(), self.truncated) {
+            ("", _) => write!(f, "empty variant name"),
+            (token, false) => write!(f, "unknown variant `{token}`"),
+            (token, true) => write!(f, "unknown variant `{token}...`"),
+        }
+    }
+}
+
+impl Debug for ParseSetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseSetError")
+            .field("token", &self.token())
+            .field("truncated", &self.truncated)
+            .finish()
+    }
+}
+
+impl Error for ParseSetError {}
+
+
+/// Iterator over the tokens of a string, split by a separator. Whitespace around the tokens (and
+/// around the separator) is ignored, and a separator made only of whitespace splits the string
+/// by any amount of whitespace. An empty (or blank) string has no tokens.
+pub struct Tokens<'a> {
+    rest: Option<&'a str>,
+    sep: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(s: &'a str, sep: &'a str) -> Self {
+        let s = s.trim();
+        Self {
+            rest: (!s.is_empty()).then_some(s),
+            sep: sep.trim(),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest.take()?;
+
+        let split = match self.sep {
+            "" => rest.split_once(char::is_whitespace),
+            sep => rest.split_once(sep),
+        };
+
+        match split {
+            Some((token, rest)) => {
+                self.rest = Some(rest.trim_start());
+                Some(token.trim())
+            }
+            None => Some(rest.trim()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens<'a>(s: &'a str, sep: &'a str) -> impl Iterator<Item = &'a str> {
+        Tokens::new(s, sep)
+    }
+
+    #[test]
+    fn split() {
+        assert!(tokens(" A | B|C ", " | ").eq(["A", "B", "C"]));
+        assert!(tokens("A,,B,", ",").eq(["A", "", "B", ""]));
+        assert!(tokens("A  B\tC", " ").eq(["A", "B", "C"]));
+        assert!(tokens("  ", ",").eq([""; 0]));
+    }
+
+    #[test]
+    fn truncated_token() {
+        let error = ParseSetError::unknown("Nope");
+        assert_eq!(error.token(), "Nope");
+        assert!(!error.is_truncated());
+
+        let long = "ñ".repeat(20);
+        let error = ParseSetError::unknown(&long);
+        assert_eq!(error.token(), "ñ".repeat(16));
+        assert!(error.is_truncated());
+    }
+}
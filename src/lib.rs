@@ -77,6 +77,11 @@
 //!
 //! You can check the generated type in the [example](example::ProgrammerStateSet) section. For more examples, see the `tests` directory at [GitHub](https://github.com/glueball/enum-bitset/tree/main/tests).
 //!
+//! Every generated set type also implements the [`EnumBitset`](trait@EnumBitset) trait, so you can write code that is generic over any set type.
+//! In turn, the base enum implements the [`BitsetBase`] trait, so that its set type can be named as [`Set<MyEnum>`](Set) in generic code.
+//!
+//! Those enums can also be used as the keys of an [`EnumMap`], a map backed by an array with one slot per variant.
+//!
 //!
 //!
 //! # Configuration
@@ -141,7 +146,7 @@
 //! # }
 //! ```
 //!
-//! You can override the default choice by specifying the `bitset` attribute with the `repr = *type*` argument. Possible values are `u8`, `u16`, `u32`, `u64`, and `u128`. The provided underlying type must be as least as wide as the number of variants that the base enum has.
+//! You can override the default choice by specifying the `bitset` attribute with the `repr = *type*` argument. Possible values are `u8`, `u16`, `u32`, `u64`, `u128` and arrays of `u64` words, `[u64; N]`. The provided underlying type must be as least as wide as the number of variants that the base enum has.
 //!
 //! For example, given the following 3-variant enum, you can force the derived type to use a `u32` instead of a `u8`.
 //!
@@ -168,6 +173,182 @@
 //!
 //! ```
 //!
+//! Enums with more than 128 variants do not fit in any primitive integer, so their bitsets are stored as an array of `u64` words, `[u64; N]`, with the smallest `N` able to hold all the variants. The first word of the array holds the 64 least significant bits, the second word the following 64, and so on. The whole API works the same way, with [`to_repr`](example::ProgrammerStateSet::to_repr) and [`from_repr`](example::ProgrammerStateSet::from_repr) using the array as the integer representation.
+//!
+//!
+//! ## bit
+//!
+//! By default, the N-th variant of the base enum is represented by the N-th least significant bit. Reordering the variants, or inserting a new one in the middle of the enum, will therefore change the meaning of the values returned by `to_repr`.
+//!
+//! If you persist or transmit that representation, you can pin the position of a variant with the `#[bitset(bit = N)]` attribute on the variant. Variants without the attribute take the position that follows the previous variant, in the same way that Rust assigns implicit discriminants. Two variants assigned to the same position, or a position that does not fit in the `repr` type, are reported as a compile error.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum MeetingOutcome {
+//!     #[bitset(bit = 3)]
+//!     CouldHaveBeenAnEmail,
+//!     #[bitset(bit = 0)]
+//!     ActionItemsNobodyOwns,
+//!     ScheduledAFollowUpMeeting, // bit 1
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(MeetingOutcomeSet::MASK, 0b1011);
+//! assert_eq!(MeetingOutcomeSet::from([MeetingOutcome::CouldHaveBeenAnEmail]).to_repr(), 0b1000);
+//!
+//! // Iteration follows the bit positions, not the declaration order.
+//! let all: Vec<_> = MeetingOutcomeSet::all().iter().collect();
+//! assert_eq!(all, [MeetingOutcome::ActionItemsNobodyOwns,
+//!                  MeetingOutcome::ScheduledAFollowUpMeeting,
+//!                  MeetingOutcome::CouldHaveBeenAnEmail]);
+//! # }
+//! ```
+//!
+//! Without an explicit `repr`, the bitset grows to fit the highest position, so positions are limited to `0..1024` to catch typos that would otherwise produce a huge set. Higher positions need an explicit `repr`, like `#[bitset(repr = [u64; 32])]`. The same limit applies to the discriminants used with [`bits = "discriminant"`](#bits).
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum MeetingOutcome {
+//!     CouldHaveBeenAnEmail,
+//!     #[bitset(bit = 200000)]
+//!     ActionItemsNobodyOwns,
+//! }
+//! ```
+//!
+//!
+//! ## bits
+//!
+//! Enums that mirror a protocol or file format often already carry the bit positions as explicit discriminants. With `#[bitset(bits = "discriminant")]`, the bit position of every variant is its discriminant, so `to_repr` returns the same bitmask used on the wire, without any conversion table. Variants without an explicit discriminant follow the previous one, as Rust does. The discriminants must be non-negative integer literals, and this mode cannot be combined with `#[bitset(bit = N)]`.
+//!
+//! The default, `bits = "declaration"`, follows the declaration order as described above.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(bits = "discriminant")]
+//! #[repr(u8)]
+//! enum Access {
+//!     Read = 0,
+//!     Write = 1,
+//!     Exec = 5,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(AccessSet::from([Access::Read, Access::Exec]).to_repr(), 0b10_0001);
+//! assert_eq!(AccessSet::from_repr(0b10), Some(AccessSet::from([Access::Write])));
+//! # }
+//! ```
+//!
+//!
+//! ## skip
+//!
+//! Variants marked with `#[bitset(skip)]` are excluded from the set: they get no bit, and are not part of `VARIANTS`, `all()` or the iteration. Inserting one of them (or converting it into a set) is a no-op, known at compile time, and [`try_insert`](example::ProgrammerStateSet::try_insert) reports it by returning `false`. This is useful for sentinels like `Unknown` that must never appear in a set. Skipped variants may have fields, and do not take a bit position, so the following variant takes the position that the skipped one would have had.
+//!
+//! Skipped variants have no [`index`](BitsetBase::index) either, and calling it on them panics. For that reason, they have no slot in an [`EnumMap`]: looking them up finds nothing, inserting them panics, and deserializing a map with one of them as a key is an error.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum Excuse {
+//!     WorksOnMyMachine,
+//!     #[bitset(skip)]
+//!     Unknown(u16),
+//!     CacheIssue,
+//! }
+//!
+//! # fn main() {
+//! let mut set = ExcuseSet::from([Excuse::Unknown(404)]);
+//! assert!(set.is_empty());
+//! assert!(!set.try_insert(Excuse::Unknown(500)));
+//! assert!(set.try_insert(Excuse::CacheIssue));
+//! assert_eq!(set.to_repr(), 0b10);
+//! assert_eq!(ExcuseSet::VARIANTS, [Excuse::WorksOnMyMachine, Excuse::CacheIssue]);
+//! # }
+//! ```
+//!
+//!
+//! ## kinds
+//!
+//! For enums whose variants carry data, `#[bitset(kinds)]` generates a fieldless enum of the *kinds* of the enum (named `{Base}Kind` by default, or chosen with `kinds = Name`), with one variant per variant of the base enum, and derives the set for it. The base enum gets a `const fn kind(&self)` method, and both the kind enum and its set can be created from a reference to a value. The `contains` method of the set accepts such references too, so values can be checked directly with `set.contains(&value)`, the same as `set.contains_kind_of(&value)`.
+//!
+//! The rest of the `#[bitset(..)]` options, on the enum and on its variants, apply to the set of kinds. The kind enum derives `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`, but not the `serde` traits. For that reason its set is not (de)serialized by default, and only the formats that do not need them are accepted: `serde = "repr"`, `"map"` and `"string"`. The other `serde` options are reported as a compile error.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug)]
+//! #[bitset(kinds = Blame)]
+//! enum Incident {
+//!     Deploy { on_friday: bool },
+//!     Intern(String),
+//!     Dns,
+//! }
+//!
+//! # fn main() {
+//! let incidents = [Incident::Dns, Incident::Intern("bob".into()), Incident::Dns];
+//! let blames: BlameSet = incidents.iter().map(Incident::kind).collect();
+//! assert_eq!(blames, Blame::Intern | Blame::Dns);
+//!
+//! let usual_suspects = Blame::Dns.as_bitset();
+//! assert!(usual_suspects.contains(&Incident::Dns));
+//! assert!(usual_suspects.contains(Blame::Dns));
+//! assert!(!usual_suspects.contains(&Incident::Deploy { on_friday: true }));
+//! # }
+//! ```
+//!
+//!
+//! ## group
+//!
+//! Variants can be tagged with one or more groups, with `#[bitset(group(name, ..))]`. For every group, the set gets an associated constant named after the group in `SCREAMING_SNAKE_CASE`, with all the variants tagged with it. The constants are computed at compile time, so they can be used in `const` contexts.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum Permission {
+//!     #[bitset(group(read_only, admin))]
+//!     Read,
+//!     #[bitset(group(admin))]
+//!     Write,
+//!     #[bitset(group(admin, billing))]
+//!     Invoice,
+//!     Login,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(PermissionSet::ADMIN, Permission::Read | Permission::Write | Permission::Invoice);
+//! assert_eq!(PermissionSet::BILLING, Permission::Invoice.as_bitset());
+//! assert!(!PermissionSet::READ_ONLY.contains(Permission::Login));
+//! # }
+//! ```
+//!
+//! Two spellings of the same constant, like `read_only` and `readOnly`, are reported as a compile error, as are the names of the constants the set already has: `MASK`, `VARIANTS` and `NAMES`.
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum Permission {
+//!     #[bitset(group(mask))]
+//!     Read,
+//!     Write,
+//! }
+//! ```
+//!
+//! A group only exists if some variant is tagged with it. The compiler removes the variants with an inactive `#[cfg(..)]` attribute (and their tags) before the macro sees them, also for [`remote!`](#remote-enums) sets, so a group left without variants has no constant.
+//!
 //!
 //! ## no_debug
 //! If `no_debug` is specified, the generated type will not implement the `Debug` trait.
@@ -224,6 +405,77 @@
 //! ```
 //!
 //!
+//! ## rename_all and rename
+//!
+//! The set knows the names of the variants of the base enum: `NAMES` holds the names of the variants in the set (in the same order as `VARIANTS`), `name(variant)` returns the name of any variant, and `from_name(name)` returns the variant with the given name, if any. They are also available through the [`EnumBitset`](trait@EnumBitset) trait.
+//!
+//! By default, the name of a variant is its identifier. `rename_all` applies a case conversion to all of them, using the same rules as serde: `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`, and `"SCREAMING-KEBAB-CASE"`. A variant can also be given any other name with `#[bitset(rename = "..")]`, which takes precedence over `rename_all`. Two variants cannot end up with the same name.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(rename_all = "snake_case")]
+//! enum Excuse {
+//!     WorksOnMyMachine,
+//!     #[bitset(rename = "dns")]
+//!     ItWasDns,
+//!     CacheIssue,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(ExcuseSet::NAMES, ["works_on_my_machine", "dns", "cache_issue"]);
+//! assert_eq!(ExcuseSet::name(Excuse::CacheIssue), "cache_issue");
+//! assert_eq!(ExcuseSet::from_name("dns"), Some(Excuse::ItWasDns));
+//! # }
+//! ```
+//!
+//!
+//! ## display
+//!
+//! With `display`, the set implements [`Display`](core::fmt::Display) and [`FromStr`](core::str::FromStr), using the [names](#rename_all-and-rename) of the variants separated by `" | "`. The separator can be chosen with `display(sep = "..")`. The base enum does not need to implement `Display`.
+//!
+//! The variants are written in the same order as the iteration. When parsing, the whitespace around every name is ignored, so `"A|B"` and `" A | B "` are both accepted, and an empty string is the empty set. A name that is not a variant of the enum (or an empty one, like in `"A,,B"`) is reported with a [`ParseSetError`], which contains the offending token.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(display(sep = ","))]
+//! enum Feature {
+//!     Metrics,
+//!     Tracing,
+//!     Profiling,
+//! }
+//!
+//! # fn main() {
+//! let set = Feature::Tracing | Feature::Metrics;
+//! assert_eq!(set.to_string(), "Metrics,Tracing");
+//! assert_eq!("Tracing, Metrics".parse(), Ok(set));
+//!
+//! let error = "Metrics,Logging".parse::<FeatureSet>().unwrap_err();
+//! assert_eq!(error.token(), "Logging");
+//! # }
+//! ```
+//!
+//! As with [`serde = "string"`](#serde), the separator cannot be empty, and a name that contains it is reported as a compile error.
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(display(sep = "/"))]
+//! enum Feature {
+//!     Metrics,
+//!     #[bitset(rename = "input/output")]
+//!     Io,
+//! }
+//! ```
+//!
+//!
 //! ## serde
 //! If the `serde` feature is enabled, the generated type will be compatible with the ubiquitous `serde` crate. The generated type will implement the `serde::Serialize` and `serde::Deserialize` traits. The only requisite is that the base enum must implement `serde::Serialize` and `serde::Deserialize`. The set will (de)serialize as an array of the base enum variants.
 //!
@@ -267,6 +519,12 @@
 //! * `serde = "de"`or `serde = "deserialize"`: the generated type will implement `serde::Deserialize` but not `serde::Serialize`.
 //! * `serde = "both"`: equivalent to `serde = true`.
 //! * `serde = "none"`: equivalent to `serde = false`.
+//! * `serde = "repr"`: the generated type will implement `serde::Serialize` and `serde::Deserialize`, using the [integer representation](#repr) of the set instead of a list (see below).
+//! * `serde = "map"`: the generated type will implement `serde::Serialize` and `serde::Deserialize`, using a map from the name of every variant to a boolean (see below).
+//! * `serde = "string"`: the generated type will implement `serde::Serialize` and `serde::Deserialize`, using a single string with the names of the variants (see below).
+//! * `serde = "adaptive"`: the generated type will implement `serde::Serialize` and `serde::Deserialize`, using a list for human-readable formats, and the integer representation for the other ones (see below).
+//!
+//! The formats can be combined with the options above, so `#[bitset(serde = "repr", serde = "ser")]` only implements `serde::Serialize`, using the integer representation. Options are applied in order, so a later `serde = false` disables both traits whatever the format.
 //!
 //! ```rust
 //! use enum_bitset::EnumBitset;
@@ -294,6 +552,156 @@
 //! # }
 //! ```
 //!
+//! With `serde = "repr"`, the set is (de)serialized as the integer returned by `to_repr` (or as a tuple of `u64` words, for sets with more than 128 bits). This is compact, and the base enum does not need to implement the serde traits. Deserializing an integer with a bit that does not correspond to any variant fails with an error.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "repr")]
+//! enum CatMood {
+//!     Purring,
+//!     Judging,
+//!     KnockingThingsOver,
+//! }
+//!
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! let set = CatMood::Judging | CatMood::KnockingThingsOver;
+//! assert_eq!(serde_json::to_string(&set).unwrap(), "6");
+//! assert_eq!(serde_json::from_str::<CatMoodSet>("6").unwrap(), set);
+//! assert!(serde_json::from_str::<CatMoodSet>("8").is_err());
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
+//!
+//! With `serde = "map"`, the set is (de)serialized as a map with every variant as a key, and `true` as the value for the variants in the set. The keys are the [names](#rename_all-and-rename) of the variants, so the base enum does not need to implement the serde traits either. When deserializing, absent variants are not in the set, and unknown keys are an error, unless `#[bitset(serde_unknown = "ignore")]` is given.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "map", serde_unknown = "ignore", rename_all = "snake_case")]
+//! enum BirdHabit {
+//!     DawnChorus,
+//!     BreadBegging,
+//!     WindowStaring,
+//! }
+//!
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! let set = BirdHabit::DawnChorus.as_bitset();
+//! assert_eq!(
+//!     serde_json::to_string(&set).unwrap(),
+//!     r#"{"dawn_chorus":true,"bread_begging":false,"window_staring":false}"#
+//! );
+//! let parsed: BirdHabitSet = serde_json::from_str(r#"{"dawn_chorus":true,"migrating":true}"#).unwrap();
+//! assert_eq!(parsed, set);
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
+//!
+//! With `serde = "string"`, the set is (de)serialized as a single string, with the [names](#rename_all-and-rename) of the variants in the set separated by `","`, or by the separator given with `#[bitset(sep = "..")]`. This fits environment variables and CSV columns. When parsing, whitespace around the names is ignored, and an empty string is the empty set. Unknown names are an error, unless `#[bitset(serde_unknown = "ignore")]` is given.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "string", rename_all = "lowercase")]
+//! enum Telemetry {
+//!     Metrics,
+//!     Tracing,
+//!     Logs,
+//! }
+//!
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! let set = Telemetry::Metrics | Telemetry::Tracing;
+//! assert_eq!(serde_json::to_string(&set).unwrap(), r#""metrics,tracing""#);
+//! assert_eq!(serde_json::from_str::<TelemetrySet>(r#"" metrics , tracing ""#).unwrap(), set);
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
+//!
+//! The separator cannot be empty, and no name can contain it (or contain whitespace, if the separator is blank), since the string could not be parsed back. Both are reported as a compile error.
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "string", rename_all = "kebab-case", sep = "-")]
+//! enum Telemetry {
+//!     Metrics,
+//!     DistributedTracing,
+//! }
+//! ```
+//!
+//! With `serde = "adaptive"`, the set is (de)serialized as a list of variants when the format is human-readable (as reported by `Serializer::is_human_readable`, like JSON or YAML), and as with `serde = "repr"` otherwise (like bincode or postcard). As with the default list, the base enum must implement the serde traits.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "adaptive")]
+//! enum Cache {
+//!     Memory,
+//!     Disk,
+//! }
+//!
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! let set = Cache::Memory | Cache::Disk;
+//! assert_eq!(serde_json::to_string(&set).unwrap(), r#"["Memory","Disk"]"#);
+//! assert_eq!(serde_json::from_str::<CacheSet>(r#"["Disk","Memory"]"#).unwrap(), set);
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
+//!
+//! Deserialization can be made stricter or more lenient with two more options, which apply to the lists of variants and to the `"map"` and `"string"` formats:
+//!
+//! * `serde_duplicates = "error"`: a variant that appears more than once is an error. By default (`"ignore"`), it is accepted.
+//! * `serde_unknown = "ignore"`: unknown variants are skipped, for example the ones added by a newer version of the enum. By default (`"error"`), they are an error. With lists, the base enum must implement `DeserializeOwned`. Only the variants written as strings can be skipped, which is what human-readable formats like JSON do: binary formats (the ones whose `Deserializer::is_human_readable` returns `false`, like bincode or postcard) write variants as indices, so their lists are always parsed strictly, and unknown variants are an error. For the same reason, this option cannot be combined with `serde = "adaptive"`, which uses the integer representation in binary formats.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde_unknown = "ignore", serde_duplicates = "error")]
+//! enum Topping {
+//!     Cheese,
+//!     Basil,
+//! }
+//!
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! let set: ToppingSet = serde_json::from_str(r#"["Basil","Pineapple"]"#).unwrap();
+//! assert_eq!(set, Topping::Basil.as_bitset());
+//! assert!(serde_json::from_str::<ToppingSet>(r#"["Basil","Basil"]"#).is_err());
+//! # }
+//! # #[cfg(not(feature = "serde"))]
+//! # fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+//! #[bitset(serde = "adaptive", serde_unknown = "ignore")]
+//! enum Topping {
+//!     Cheese,
+//!     Basil,
+//! }
+//! ```
+//!
+//! To use a different shape for the same set type in a particular struct, use the adapter modules in [`enum_bitset::serde`](crate::serde) with `#[serde(with = "..")]`.
+//!
 //! Note: If, for some reason, the `serde` crate is renamed, you can specify it using `#[bitset(serde_crate = serde_crate_path)]`.
 //!
 //! ## no_base_ops
@@ -358,6 +766,84 @@
 //! ```
 //!
 //!
+//! ## no_base_index
+//!
+//! The base enum gets inherent `const fn index(&self)` and `const fn from_index(index)` methods, the same as in [`BitsetBase`]. An enum that already has methods with those names fails to compile with a "duplicate definitions" error, so `no_base_index` leaves out the inherent methods. The [`BitsetBase`] methods are still available.
+//!
+//! ```rust
+//! use enum_bitset::{BitsetBase, EnumBitset};
+//!
+//! #[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(no_base_index)]
+//! enum Slide {
+//!     Title,
+//!     Agenda,
+//!     Questions,
+//! }
+//!
+//! impl Slide {
+//!     fn index(&self) -> &'static str {
+//!         "Table of contents"
+//!     }
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(Slide::Agenda.index(), "Table of contents");
+//! assert_eq!(BitsetBase::index(&Slide::Agenda), 1);
+//! assert_eq!(<Slide as BitsetBase>::from_index(2), Some(Slide::Questions));
+//! # }
+//! ```
+//!
+//!
+//! ## atomic
+//! With `atomic`, the macro also generates an atomic version of the set, named after it (`AtomicStateSet` for `StateSet`), to share and modify a set between threads without a lock. It is backed by the atomic integer of the same width as the representation, from [`AtomicU8`](core::sync::atomic::AtomicU8) to [`AtomicU64`](core::sync::atomic::AtomicU64), and provides `load`, `store`, `compare_exchange`, `fetch_insert`, `fetch_remove`, `fetch_union`, and `insert` and `remove` methods that report whether the set changed.
+//!
+//! ```rust
+//! use std::sync::atomic::Ordering;
+//!
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+//! # #[bitset(serde = false)]
+//! #[bitset(atomic)]
+//! enum Subsystem {
+//!     Storage,
+//!     Network,
+//!     Scheduler,
+//! }
+//!
+//! # fn main() {
+//! let ready = AtomicSubsystemSet::default();
+//!
+//! std::thread::scope(|scope| {
+//!     scope.spawn(|| ready.insert(Subsystem::Storage, Ordering::Release));
+//!     scope.spawn(|| ready.insert(Subsystem::Network, Ordering::Release));
+//! });
+//!
+//! assert_eq!(ready.load(Ordering::Acquire), Subsystem::Storage | Subsystem::Network);
+//! assert!(!ready.insert(Subsystem::Network, Ordering::AcqRel));
+//! # }
+//! ```
+//!
+//! There is no stable `AtomicU128`, so sets that need more than 64 bits cannot be atomic:
+//!
+//! ```compile_fail
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Clone, Copy)]
+//! # #[bitset(serde = false)]
+//! #[bitset(atomic, repr = u128)]
+//! enum Subsystem {
+//!     Storage,
+//!     Network,
+//!     Scheduler,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Not every target has atomics of every width (some embedded targets have none, or only up to 32 bits). The atomic set only exists where `cfg(target_has_atomic = "N")` holds for the width `N` of its representation; elsewhere, `atomic` is a compile error naming the missing width. A smaller `repr` may help on such targets.
+//!
 //!
 //! ## crate
 //! In case that you rename the `enum-bitset` crate in your `Cargo.toml`, the code generated by the macro will not be able to call some utility code contained in the crate. To work around this, you can specify the `crate` argument.
@@ -387,11 +873,38 @@
 //! ```
 //!
 //!
+//! # Remote enums
+//!
+//! The derive macro cannot be used on enums declared in other crates (or in modules you cannot modify). For them, the [`remote!`] macro generates the set from the name of the set, the path to the enum, and the list of its variants. The same `#[bitset(..)]` attributes can be used, both on the set and on the variants.
+//!
+//! ```rust
+//! use std::net::Shutdown;
+//!
+//! enum_bitset::remote!(
+//!     # #[bitset(serde = false)]
+//!     pub ShutdownSet for std::net::Shutdown { Read, Write, Both }
+//! );
+//!
+//! # fn main() {
+//! let set = ShutdownSet::from_array([Shutdown::Read, Shutdown::Both]);
+//! assert!(set.contains(Shutdown::Both));
+//! assert_eq!(set.len(), 2);
+//! # }
+//! ```
+//!
+//! The list of variants must be exhaustive (variants with fields must be [skipped](#skip)): the generated code matches on the enum without a wildcard branch, so adding a variant upstream breaks the build, instead of silently leaving it out of the set. For the same reason, enums marked `#[non_exhaustive]` cannot be used.
+//!
+//! The set has the same API as a derived one. However, the orphan rules do not allow implementing anything on an enum from another crate, so the base enum has no `as_bitset` method, and does not implement [`BitsetBase`] (which means it cannot be used as the key of an [`EnumMap`]) nor the operators between variants (`Shutdown::Read | Shutdown::Write`).
+//!
+//! As for derived sets, the variants of the list with an inactive `#[cfg(..)]` attribute are removed, and the following variants shift down one position.
+//!
+//!
 //! # Cargo feature
 //!
 //! This crate has the following optional feature:
 //!
 //! * `serde`: Enables support for the `serde` crate. Check the [serde section](#serde) for more details.
+//! * `serde_with`: Implements `serde_with::SerializeAs` and `serde_with::DeserializeAs` for the adapters in the [`serde`](crate::serde) module. Implies `serde`.
 //!      
 //! # Technical details
 //!
@@ -401,6 +914,7 @@
 //!
 //! * The layout and ABI of the bitset are guaranteed to be exactly the same as the underlying integer type used to store the data.
 //!   That is, a bitset generated for an enum of 6 variants and backed by a `u8` type will have the same layout and ABI as the primitive `u8` type.
+//!   Likewise, a bitset backed by `[u64; N]` has the same layout and ABI as that array.
 //!
 //! * The bitset type is guaranteed to be `Send` and `Sync`, regardless of whether the base enum is.
 //!
@@ -409,6 +923,10 @@
 //!
 //!   More concretely, the first variant in the base enum will be represented by the least-significant bit position.
 //!   The second variant will be represented by the second least significant digit, and so on.
+//!   Variants with a [pinned bit position](#bit) keep their bit regardless of their order in the enum.
+//!
+//!   Variants compiled out with `#[cfg(..)]` do not exist as far as the bitset is concerned: they have no bit, and the variants that follow them shift down one position.
+//!   If the representation must be the same in every configuration, [pin the bit position](#bit) of the variants that follow a gated one (or of the gated variant itself).
 //!
 //! * The bitset type will have the same size as the underlying integer type, provided that the order of the variants in the base enum does not change.
 //!
@@ -437,7 +955,7 @@
 //! 2. How to implement the core set operations (insert, removal, union, intersection, difference, symmetric difference, iteration, etc.).
 //! 3. How to convert from the base enum values to the position of the bits in the underlying values.
 //!
-//! In the first matter, this crate decides to represent the bitset as a single primitive unsigned integer. Choosing, by default, the smallest type possible; but the user might choose to use a larger type. Because of this, the [`EnumBitset`] macro only works with types up to 128 variants. This should not be a concert in most use cases.
+//! In the first matter, this crate decides to represent the bitset as a single primitive unsigned integer. Choosing, by default, the smallest type possible; but the user might choose to use a larger type. Only enums with more than 128 variants, which do not fit in any primitive integer, are represented as an array of `u64` words.
 //!
 //! For the implementation of core set operations, this crate decides to implement them using the bitwise operations. This particular choice should be pretty non-controversial and standard: bitwise operations make all methods `O(1)`, and is usually the fastest implementation. Additionally, due to the [invariant](#invariant) upheld by bitset generated types, masking the values is usually not needed in most methods, saving some cicles in most methods.
 //!
@@ -471,7 +989,7 @@
 //!
 //! However, if you really need to squeeze every CPU cicle, I recommend looking to other crates to that implement this tradeoff differently. We do not have benchmarks, but it seems that a bit of performance might be gained by using the second method described above.
 //!
-//! In the future, should the need arise, customization options might be added to allow specifying the position of the bits in the underlying integer or even to automatically set the representation of every variant. In that case, the [Guarantees](#guarantees) might need to evolve to take into account those config options. Most likely, the default will remain as the current implementation right now.
+//! The position of the bit of every variant can be pinned with the [`bit`](#bit) attribute, which only changes the values returned by the generated `match`.
 //!
 //!
 //! ## `no_std` and FFI usage
@@ -498,17 +1016,45 @@
 //!
 //!
 //!
-//! ## Limitations
+//! ## Generic enums
 //!
-//! * The bitset type is currently limited to enums with up to 128 variants (since the largest primitive unsigned integer type is `u128`). This limitation may be lifted in the future.
+//! Enums with generic parameters (types, lifetimes or constants) are supported. The set and its iterator take the same parameters as the base enum, so `Kind<T>` gets a `KindSet<T>`. Since every variant of the set must be a unit variant, the parameters are usually referenced from a [skipped](#skip) variant, like `PhantomData` markers.
+//!
+//! The set implements `Clone`, `Copy`, `Eq`, `Hash`, `Ord` and the set operations regardless of the generic parameters. The features that need to produce values of the base enum (iteration, `Debug`, the [`EnumBitset`](trait@EnumBitset) and [`BitsetBase`] traits, and serde) are only available when the base enum implements `Clone` (and `Debug`, `'static` or the serde traits, respectively).
+//!
+//! ```rust
+//! use core::marker::PhantomData;
+//! use enum_bitset::EnumBitset;
+//!
+//! #[derive(EnumBitset, Debug, Copy, Clone, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum Reading<Unit> {
+//!     Min,
+//!     Max,
+//!     Average,
+//!     #[bitset(skip)]
+//!     _Unit(PhantomData<Unit>),
+//! }
+//!
+//! #[derive(Debug, Copy, Clone, PartialEq)]
+//! struct Celsius;
+//!
+//! # fn main() {
+//! let set: ReadingSet<Celsius> = Reading::Min | Reading::Max;
+//! assert_eq!(set.len(), 2);
+//! assert_eq!(set.iter().last(), Some(Reading::Max));
+//! # }
+//! ```
+//!
+//! ## Limitations
 //!
 //! * [`EnumBitset`] only supports enums that are cloneable. This is rarely a limiting factor, since all enums without fields are cloneable (and, in fact, they can be `Copy`, if desired). But it is important to have it in mind if, for some reason, you don't want to make your enum clone or copy.[^1]
 //!
 //! [^1]: In the future, if the need arises, this limitation might be softened. Cloning/copying is actually only required for iteration. So, if iteration is not needed, it should be possible to create the bitset type for an enum without cloning/copying. However, this should be a relatively uncommon requirement, so it hasn't been implemented yet.
 //!
-//! * The macro can be only used in unit enums. That is, with enums where all the variants have no fields.
+//! * The set can only be derived for unit enums. That is, with enums where all the variants have no fields (except for [skipped](#skip) variants). For enums with fields, use [`kinds`](#kinds) to derive the set of their kinds instead.
 //!
-//!   You can work around this limitation by using the [EnumDiscriminants] macro from the excellent `strum` crate:
+//!   You can also use the [EnumDiscriminants] macro from the excellent `strum` crate:
 //!
 //!  ```rust
 //! use strum::EnumDiscriminants;
@@ -584,11 +1130,21 @@
 //! [BTreeSet]: https://doc.rust-lang.org/std/collections/struct.BTreeSet.html
 //! [EnumDiscriminants]: https://docs.rs/strum/latest/strum/derive.EnumDiscriminants.html
 //! [enumset]: https://crates.io/crates/enumset
+//! [`EnumBitset`]: derive@EnumBitset
 
 #![no_std]
 
+#[doc(hidden)]
+pub mod bits;
 #[doc(hidden)]
 pub mod debug_impl;
+#[doc(hidden)]
+pub mod display_impl;
+
+pub mod map;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod traits;
 
 #[cfg(any(test, doc, docsrs))]
 pub mod example;
@@ -597,3 +1153,12 @@ pub mod example;
 ///
 /// Look at the crate-level documentation for more information.
 pub use enum_bitset_derive::EnumBitset;
+/// Generates a bitset type for an enum declared elsewhere, typically in another crate.
+///
+/// Look at the [crate-level documentation](crate#remote-enums) for more information.
+pub use enum_bitset_derive::remote;
+#[doc(hidden)]
+pub use enum_bitset_derive::__RemoteEnumBitset;
+pub use display_impl::ParseSetError;
+pub use map::EnumMap;
+pub use traits::{BitsetBase, EnumBitset, Set};
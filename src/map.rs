@@ -0,0 +1,630 @@
+//! A map keyed by an enum that derives [`EnumBitset`](derive@crate::EnumBitset).
+//!
+//! Check [`EnumMap`] for more details.
+
+use core::{
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::{BitsetBase, EnumBitset, Set};
+
+
+/// A map whose keys are the variants of an enum that derives
+/// [`EnumBitset`](derive@crate::EnumBitset).
+///
+/// Since the set of possible keys is known at compile time, the map is implemented as an array
+/// of `Option<V>`, with one slot per variant of `K`. The slot of a given key is determined by
+/// [`BitsetBase::index`], that is, by the position of the variant in the declaration of the enum.
+/// Hence, all the operations on a single key are constant time and no hashing is involved.
+///
+/// Iteration yields the entries in the declaration order of the variants of `K`, regardless of
+/// insertion order.
+///
+/// Variants skipped with `#[bitset(skip)]` have no slot. Looking them up finds nothing, but
+/// inserting them panics.
+///
+/// ```rust
+/// use enum_bitset::{EnumBitset, EnumMap};
+///
+/// #[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+/// # #[bitset(serde = false)]
+/// enum Phase {
+///     Setup,
+///     Run,
+///     Teardown,
+/// }
+///
+/// # fn main() {
+/// let mut timeouts = EnumMap::new();
+/// timeouts.insert(Phase::Teardown, 5);
+/// timeouts.insert(Phase::Setup, 10);
+///
+/// assert_eq!(timeouts.get(&Phase::Setup), Some(&10));
+/// assert_eq!(timeouts.get(&Phase::Run), None);
+/// assert_eq!(timeouts.keys(), Phase::Setup | Phase::Teardown);
+///
+/// *timeouts.entry(Phase::Run).or_insert(0) += 1;
+/// assert_eq!(timeouts[Phase::Run], 1);
+///
+/// let entries: Vec<_> = timeouts.iter().collect();
+/// assert_eq!(entries, [(Phase::Setup, &10), (Phase::Run, &1), (Phase::Teardown, &5)]);
+/// # }
+/// ```
+pub struct EnumMap<K: BitsetBase, V> {
+    values: K::Array<Option<V>>,
+}
+
+
+impl<K: BitsetBase, V> EnumMap<K, V> {
+    /// Creates a new empty map.
+    pub fn new() -> Self {
+        Self {
+            values: K::array_from_fn(|_| None),
+        }
+    }
+
+    /// Creates a new map where every key of `K` is present, with the value returned by `f` for
+    /// that key.
+    pub fn from_fn<F: FnMut(K) -> V>(mut f: F) -> Self {
+        Self {
+            values: K::array_from_fn(|index| Some(f(key_at::<K>(index)))),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.slots().iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.slots().iter().all(Option::is_none)
+    }
+
+    /// Removes all the entries of the map.
+    pub fn clear(&mut self) {
+        self.slots_mut().iter_mut().for_each(|slot| *slot = None);
+    }
+
+    /// Returns `true` if the map contains a value for the given key. Always `false` for skipped
+    /// variants.
+    pub fn contains_key(&self, key: &K) -> bool {
+        slot_of(key).is_some_and(|index| self.slots()[index].is_some())
+    }
+
+    /// Returns a reference to the value corresponding to the key. Always `None` for skipped
+    /// variants.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.slots()[slot_of(key)?].as_ref()
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. Always `None` for
+    /// skipped variants.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.slots_mut()[slot_of(key)?].as_mut()
+    }
+
+    /// Inserts a value for the given key, returning the previous value (if any).
+    ///
+    /// # Panics
+    /// Panics if the key is a variant skipped with `#[bitset(skip)]`, which has no slot in the
+    /// map.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.slots_mut()[expect_slot(&key)].replace(value)
+    }
+
+    /// Removes the value of the given key from the map, returning it (if any). Always `None` for
+    /// skipped variants.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.slots_mut()[slot_of(key)?].take()
+    }
+
+    /// Gets the entry of the given key, for in-place manipulation.
+    ///
+    /// # Panics
+    /// Panics if the key is a variant skipped with `#[bitset(skip)]`, which has no slot in the
+    /// map.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let slot = &mut self.slots_mut()[expect_slot(&key)];
+
+        if slot.is_some() {
+            Entry::Occupied(OccupiedEntry { key, slot })
+        } else {
+            Entry::Vacant(VacantEntry { key, slot })
+        }
+    }
+
+    /// Returns the set of keys that have a value in the map.
+    pub fn keys(&self) -> Set<K> {
+        self.iter().map(|(key, _)| key).collect()
+    }
+
+    /// Returns an iterator over the values of the map, in the declaration order of their keys.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.slots().iter().flatten()
+    }
+
+    /// Returns an iterator over mutable references to the values of the map, in the declaration
+    /// order of their keys.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.slots_mut().iter_mut().flatten()
+    }
+
+    /// Returns an iterator over the entries of the map, in the declaration order of the keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots().iter().enumerate(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the entries of the map, with mutable references to the values,
+    /// in the declaration order of the keys.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots_mut().iter_mut().enumerate(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain<F: FnMut(K, &mut V) -> bool>(&mut self, mut f: F) {
+        for (index, slot) in self.slots_mut().iter_mut().enumerate() {
+            if let Some(value) = slot
+                && !f(key_at::<K>(index), value)
+            {
+                *slot = None;
+            }
+        }
+    }
+
+    #[inline]
+    fn slots(&self) -> &[Option<V>] {
+        self.values.as_ref()
+    }
+
+    #[inline]
+    fn slots_mut(&mut self) -> &mut [Option<V>] {
+        self.values.as_mut()
+    }
+}
+
+
+#[inline]
+fn key_at<K: BitsetBase>(index: usize) -> K {
+    <Set<K> as EnumBitset>::VARIANTS[index].clone()
+}
+
+/// The slot of `key`, or `None` if it is a skipped variant (which has no index).
+#[inline]
+fn slot_of<K: BitsetBase>(key: &K) -> Option<usize> {
+    <Set<K> as EnumBitset>::all()
+        .contains(key)
+        .then(|| key.index())
+}
+
+#[inline]
+fn expect_slot<K: BitsetBase>(key: &K) -> usize {
+    match slot_of(key) {
+        Some(index) => index,
+        None => panic!(
+            "`{}` is skipped, and cannot be a key of an EnumMap",
+            <Set<K> as EnumBitset>::name(key)
+        ),
+    }
+}
+
+
+impl<K: BitsetBase, V> Default for EnumMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: BitsetBase, V: Clone> Clone for EnumMap<K, V> {
+    fn clone(&self) -> Self {
+        let slots = self.slots();
+        Self {
+            values: K::array_from_fn(|index| slots[index].clone()),
+        }
+    }
+}
+
+impl<K: BitsetBase, V: PartialEq> PartialEq for EnumMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots() == other.slots()
+    }
+}
+
+impl<K: BitsetBase, V: Eq> Eq for EnumMap<K, V> {}
+
+impl<K: BitsetBase, V: Hash> Hash for EnumMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.slots().hash(state);
+    }
+}
+
+impl<K: BitsetBase + Debug, V: Debug> Debug for EnumMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+
+impl<K: BitsetBase, V> Index<K> for EnumMap<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value of the given key.
+    ///
+    /// # Panics
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: K) -> &V {
+        self.get(&key).expect("key not present in the EnumMap")
+    }
+}
+
+impl<K: BitsetBase, V> IndexMut<K> for EnumMap<K, V> {
+    /// Returns a mutable reference to the value of the given key.
+    ///
+    /// # Panics
+    /// Panics if the key is not present in the map.
+    fn index_mut(&mut self, key: K) -> &mut V {
+        self.get_mut(&key)
+            .expect("key not present in the EnumMap")
+    }
+}
+
+
+impl<K: BitsetBase, V> FromIterator<(K, V)> for EnumMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: BitsetBase, V> Extend<(K, V)> for EnumMap<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: BitsetBase, V, const N: usize> From<[(K, V); N]> for EnumMap<K, V> {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+
+/// A view into a single entry of an [`EnumMap`], which may either be vacant or occupied.
+///
+/// Returned by the [`EnumMap::entry`] method.
+pub enum Entry<'a, K: BitsetBase, V> {
+    /// The key has a value in the map.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// The key has no value in the map.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// An entry of an [`EnumMap`] whose key has a value. Part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K: BitsetBase, V> {
+    key: K,
+    slot: &'a mut Option<V>,
+}
+
+/// An entry of an [`EnumMap`] whose key has no value. Part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K: BitsetBase, V> {
+    key: K,
+    slot: &'a mut Option<V>,
+}
+
+
+impl<'a, K: BitsetBase, V> Entry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of calling `default` if the entry is vacant, and returns a mutable
+    /// reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Inserts the result of calling `default` with the key if the entry is vacant, and returns
+    /// a mutable reference to the value.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: BitsetBase, V: Default> Entry<'a, K, V> {
+    /// Inserts the default value if the entry is vacant, and returns a mutable reference to the
+    /// value.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: BitsetBase, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns a reference to the value of the entry.
+    pub fn get(&self) -> &V {
+        self.slot
+            .as_ref()
+            .expect("an occupied entry always has a value")
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.slot
+            .as_mut()
+            .expect("an occupied entry always has a value")
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        self.slot
+            .as_mut()
+            .expect("an occupied entry always has a value")
+    }
+
+    /// Replaces the value of the entry, returning the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map, returning its value.
+    pub fn remove(self) -> V {
+        self.slot
+            .take()
+            .expect("an occupied entry always has a value")
+    }
+}
+
+impl<'a, K: BitsetBase, V> VacantEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry, returning a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.slot.insert(value)
+    }
+}
+
+
+/// Iterator over the entries of an [`EnumMap`]. Returned by [`EnumMap::iter`].
+pub struct Iter<'a, K, V> {
+    slots: Enumerate<slice::Iter<'a, Option<V>>>,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: BitsetBase, V> Iterator for Iter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .find_map(|(index, slot)| Some((key_at::<K>(index), slot.as_ref()?)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.slots.size_hint().1)
+    }
+}
+
+impl<K: BitsetBase, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            _key: PhantomData,
+        }
+    }
+}
+
+
+/// Iterator over the entries of an [`EnumMap`], with mutable references to the values. Returned
+/// by [`EnumMap::iter_mut`].
+pub struct IterMut<'a, K, V> {
+    slots: Enumerate<slice::IterMut<'a, Option<V>>>,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: BitsetBase, V> Iterator for IterMut<'a, K, V> {
+    type Item = (K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots
+            .find_map(|(index, slot)| Some((key_at::<K>(index), slot.as_mut()?)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.slots.size_hint().1)
+    }
+}
+
+impl<K: BitsetBase, V> FusedIterator for IterMut<'_, K, V> {}
+
+
+/// Owning iterator over the entries of an [`EnumMap`].
+pub struct IntoIter<K: BitsetBase, V> {
+    values: K::Array<Option<V>>,
+    index: usize,
+}
+
+impl<K: BitsetBase, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slots = self.values.as_mut();
+
+        while self.index < slots.len() {
+            let index = self.index;
+            self.index += 1;
+
+            if let Some(value) = slots[index].take() {
+                return Some((key_at::<K>(index), value));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.values.as_ref().len() - self.index))
+    }
+}
+
+impl<K: BitsetBase, V> FusedIterator for IntoIter<K, V> {}
+
+
+impl<K: BitsetBase, V> IntoIterator for EnumMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            values: self.values,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K: BitsetBase, V> IntoIterator for &'a EnumMap<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: BitsetBase, V> IntoIterator for &'a mut EnumMap<K, V> {
+    type Item = (K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use core::{fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, MapAccess, Visitor},
+        ser::SerializeMap,
+    };
+
+    use super::{EnumMap, slot_of};
+    use crate::{BitsetBase, EnumBitset, Set};
+
+
+    impl<K, V> Serialize for EnumMap<K, V>
+    where
+        K: BitsetBase + Serialize,
+        V: Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+
+            for (key, value) in self.iter() {
+                map.serialize_entry(&key, value)?;
+            }
+
+            map.end()
+        }
+    }
+
+
+    impl<'de, K, V> Deserialize<'de> for EnumMap<K, V>
+    where
+        K: BitsetBase + Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct VisitMap<K, V>(PhantomData<fn() -> (K, V)>);
+
+            impl<'de, K, V> Visitor<'de> for VisitMap<K, V>
+            where
+                K: BitsetBase + Deserialize<'de>,
+                V: Deserialize<'de>,
+            {
+                type Value = EnumMap<K, V>;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a map keyed by enum variants")
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                    let mut map = EnumMap::new();
+
+                    while let Some((key, value)) = access.next_entry::<K, V>()? {
+                        let Some(index) = slot_of(&key) else {
+                            return Err(A::Error::custom(format_args!(
+                                "`{}` is skipped, and cannot be a key of an EnumMap",
+                                <Set<K> as EnumBitset>::name(&key)
+                            )));
+                        };
+
+                        map.slots_mut()[index] = Some(value);
+                    }
+
+                    Ok(map)
+                }
+            }
+
+            deserializer.deserialize_map(VisitMap(PhantomData))
+        }
+    }
+}
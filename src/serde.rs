@@ -0,0 +1,379 @@
+//! Adapters to (de)serialize any set with a given shape, regardless of its `#[bitset(serde = "..")]`
+//! configuration.
+//!
+//! Each module provides the `serialize` and `deserialize` functions expected by
+//! `#[serde(with = "..")]`, so the same set type can be stored in different shapes in different
+//! structs. They work through the [`EnumBitset`] trait, and use the same shapes as the matching
+//! `serde` option of the derive macro:
+//!
+//! * [`as_repr`]: the integer representation of the set, like `serde = "repr"`.
+//! * [`as_list`]: a list of variants, like the default. The base enum must implement the serde
+//!   traits.
+//! * [`as_map`]: a map from the name of every variant to a boolean, like `serde = "map"`.
+//! * [`as_string`]: a single string with the names of the variants separated by `","`, like
+//!   `serde = "string"`.
+//!
+//! ```rust
+//! use enum_bitset::EnumBitset;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+//! # #[bitset(serde = false)]
+//! enum Role {
+//!     Reader,
+//!     Editor,
+//!     Admin,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Row {
+//!     #[serde(with = "enum_bitset::serde::as_repr")]
+//!     roles: RoleSet,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Dto {
+//!     #[serde(with = "enum_bitset::serde::as_string")]
+//!     roles: RoleSet,
+//! }
+//!
+//! # fn main() {
+//! let roles = Role::Reader | Role::Admin;
+//! assert_eq!(serde_json::to_string(&Row { roles }).unwrap(), r#"{"roles":5}"#);
+//! assert_eq!(serde_json::to_string(&Dto { roles }).unwrap(), r#"{"roles":"Reader,Admin"}"#);
+//! # }
+//! ```
+//!
+//! With the `serde_with` feature, the [`AsRepr`], [`AsList`], [`AsMap`] and [`AsString`] types
+//! implement `serde_with::SerializeAs` and `serde_with::DeserializeAs`, so they can also be used
+//! with `#[serde_as(as = "..")]`, for example inside an `Option` or a `Vec`.
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::de::{Error, Unexpected, Visitor};
+
+use crate::EnumBitset;
+
+
+/// (De)serializes a set as its integer representation (or as a tuple of `u64` words, for sets
+/// with more than 128 bits). Representations with bits that do not correspond to any variant are
+/// rejected.
+pub mod as_repr {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub fn serialize<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: EnumBitset,
+        T::Repr: Serialize,
+        S: Serializer,
+    {
+        set.to_repr().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: EnumBitset,
+        T::Repr: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let repr = T::Repr::deserialize(deserializer)?;
+
+        T::from_repr(repr).ok_or_else(|| {
+            D::Error::invalid_value(
+                Unexpected::Other("reserved bits set"),
+                &"a valid set representation",
+            )
+        })
+    }
+}
+
+
+/// (De)serializes a set as a list of the variants it contains, using the serde implementations
+/// of the base enum.
+pub mod as_list {
+    use ::serde::{
+        Deserialize, Deserializer, Serialize, Serializer, de::SeqAccess, ser::SerializeSeq,
+    };
+
+    use super::*;
+
+    pub fn serialize<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: EnumBitset,
+        T::Base: Serialize,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(set.len()))?;
+
+        for variant in set.iter() {
+            seq.serialize_element(&variant)?;
+        }
+
+        seq.end()
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: EnumBitset,
+        T::Base: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct VisitList<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T> Visitor<'de> for VisitList<T>
+        where
+            T: EnumBitset,
+            T::Base: Deserialize<'de>,
+        {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a list of variants")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+                let mut set = T::empty();
+                while let Some(variant) = seq.next_element::<T::Base>()? {
+                    set.insert(variant);
+                }
+
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(VisitList(PhantomData))
+    }
+}
+
+
+/// (De)serializes a set as a map from the [name](EnumBitset::name) of every variant to a boolean,
+/// which is `true` for the variants in the set. When deserializing, absent variants are not in
+/// the set, and unknown names are an error.
+pub mod as_map {
+    use ::serde::{Deserializer, Serializer, de::MapAccess, ser::SerializeMap};
+
+    use super::*;
+
+    pub fn serialize<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: EnumBitset,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(T::VARIANTS.len()))?;
+
+        for (variant, name) in T::VARIANTS.iter().zip(T::NAMES) {
+            map.serialize_entry(name, &set.contains(variant))?;
+        }
+
+        map.end()
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: EnumBitset,
+        D: Deserializer<'de>,
+    {
+        struct VisitMap<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T: EnumBitset> Visitor<'de> for VisitMap<T> {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map from variant names to booleans")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+                let mut set = T::empty();
+                while let Some(Name(variant)) = map.next_key::<Name<T>>()? {
+                    if map.next_value::<bool>()? {
+                        set.insert(variant);
+                    }
+                }
+
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_map(VisitMap(PhantomData))
+    }
+}
+
+
+/// (De)serializes a set as a single string, with the [names](EnumBitset::name) of the variants in
+/// the set separated by `","`. When parsing, whitespace around the names is ignored, and an empty
+/// string is the empty set.
+///
+/// The separator cannot be chosen, so no name of the variants can contain a `","`. Unlike
+/// `serde = "string"`, which checks it at compile time, such sets are an error when serializing
+/// and deserializing.
+pub mod as_string {
+    use ::serde::{Deserializer, Serializer};
+
+    use super::*;
+    use crate::display_impl::Tokens;
+
+    pub fn serialize<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: EnumBitset,
+        S: Serializer,
+    {
+        struct Joined<'a, T>(&'a T);
+
+        impl<T: EnumBitset> fmt::Display for Joined<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (index, variant) in self.0.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    f.write_str(T::name(variant))?;
+                }
+
+                Ok(())
+            }
+        }
+
+        if let Some(name) = name_with_sep::<T>() {
+            return Err(::serde::ser::Error::custom(SepInName(name)));
+        }
+
+        serializer.collect_str(&Joined(set))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: EnumBitset,
+        D: Deserializer<'de>,
+    {
+        struct VisitString<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T: EnumBitset> Visitor<'de> for VisitString<T> {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string with variant names, separated by \",\"")
+            }
+
+            fn visit_str<E: Error>(self, s: &str) -> Result<T, E> {
+                Tokens::new(s, ",")
+                    .map(|token| {
+                        T::from_name(token).ok_or_else(|| E::unknown_variant(token, T::NAMES))
+                    })
+                    .collect()
+            }
+        }
+
+        if let Some(name) = name_with_sep::<T>() {
+            return Err(Error::custom(SepInName(name)));
+        }
+
+        deserializer.deserialize_str(VisitString(PhantomData))
+    }
+
+    /// A name of the variants of `T` that contains the separator, if any.
+    fn name_with_sep<T: EnumBitset>() -> Option<&'static str> {
+        T::NAMES.iter().copied().find(|name| name.contains(','))
+    }
+
+    struct SepInName(&'static str);
+
+    impl fmt::Display for SepInName {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "the variant name `{}` contains the separator \",\"", self.0)
+        }
+    }
+}
+
+
+/// The name of a variant of `T::Base`, as a map key.
+struct Name<T: EnumBitset>(T::Base);
+
+impl<'de, T: EnumBitset> ::serde::Deserialize<'de> for Name<T> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VisitName<T>(PhantomData<fn() -> T>);
+
+        impl<'de, T: EnumBitset> Visitor<'de> for VisitName<T> {
+            type Value = Name<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the name of a variant")
+            }
+
+            fn visit_str<E: Error>(self, name: &str) -> Result<Name<T>, E> {
+                T::from_name(name)
+                    .map(Name)
+                    .ok_or_else(|| E::unknown_variant(name, T::NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(VisitName(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde_with")]
+mod serde_with_impl {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    use super::*;
+
+    macro_rules! adapter {
+        ($adapter:ident, $module:ident, $doc:literal, [$($ser_bound:tt)*], [$($de_bound:tt)*]) => {
+            #[doc = $doc]
+            pub struct $adapter;
+
+            impl<T> SerializeAs<T> for $adapter
+            where
+                T: EnumBitset,
+                $($ser_bound)*
+            {
+                fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                    $module::serialize(source, serializer)
+                }
+            }
+
+            impl<'de, T> DeserializeAs<'de, T> for $adapter
+            where
+                T: EnumBitset,
+                $($de_bound)*
+            {
+                fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+                    $module::deserialize(deserializer)
+                }
+            }
+        };
+    }
+
+    adapter!(
+        AsRepr,
+        as_repr,
+        "A `serde_with` adapter that (de)serializes a set as in [`as_repr`].",
+        [T::Repr: Serialize],
+        [T::Repr: Deserialize<'de>]
+    );
+    adapter!(
+        AsList,
+        as_list,
+        "A `serde_with` adapter that (de)serializes a set as in [`as_list`].",
+        [T::Base: Serialize],
+        [T::Base: Deserialize<'de>]
+    );
+    adapter!(
+        AsMap,
+        as_map,
+        "A `serde_with` adapter that (de)serializes a set as in [`as_map`].",
+        [],
+        []
+    );
+    adapter!(
+        AsString,
+        as_string,
+        "A `serde_with` adapter that (de)serializes a set as in [`as_string`].",
+        [],
+        []
+    );
+}
+
+#[cfg(feature = "serde_with")]
+pub use serde_with_impl::{AsList, AsMap, AsRepr, AsString};
@@ -0,0 +1,162 @@
+use core::{borrow::Borrow, hash::Hash, iter::FusedIterator};
+
+/// Common interface implemented by every set type generated by the [`EnumBitset`](derive@crate::EnumBitset)
+/// derive macro.
+///
+/// The generated types already provide all these operations as inherent methods (most of them
+/// usable in `const` contexts). This trait exists so that code that is generic over the set type
+/// can be written once, instead of once per enum.
+///
+/// ```rust
+/// use enum_bitset::EnumBitset;
+///
+/// #[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+/// # #[bitset(serde = false)]
+/// enum Permission {
+///     Read,
+///     Write,
+///     Exec,
+/// }
+///
+/// fn count_missing<S: EnumBitset>(set: &S) -> usize {
+///     S::VARIANTS.len() - set.len()
+/// }
+///
+/// # fn main() {
+/// let set = PermissionSet::from([Permission::Read]);
+/// assert_eq!(count_missing(&set), 2);
+/// # }
+/// ```
+pub trait EnumBitset:
+    Copy
+    + Eq
+    + Hash
+    + Default
+    + From<Self::Base>
+    + FromIterator<Self::Base>
+    + IntoIterator<Item = Self::Base, IntoIter = Self::Iter>
+    + 'static
+{
+    /// The enum from which the set type was derived.
+    type Base: Clone + 'static;
+
+    /// The integer type used to store the set.
+    type Repr: Copy + Eq + 'static;
+
+    /// Iterator over the variants contained in the set, in the order of their bits.
+    type Iter: DoubleEndedIterator<Item = Self::Base> + ExactSizeIterator + FusedIterator;
+
+    /// Mask with all the bits that correspond to a variant of [`Base`](EnumBitset::Base) set to 1.
+    const MASK: Self::Repr;
+
+    /// All the variants of [`Base`](EnumBitset::Base), in declaration order.
+    const VARIANTS: &'static [Self::Base];
+
+    /// The names of the variants in [`VARIANTS`](EnumBitset::VARIANTS), in the same order.
+    const NAMES: &'static [&'static str];
+
+    /// Creates a new empty set.
+    fn empty() -> Self;
+
+    /// Creates a new set that contains all the variants of [`Base`](EnumBitset::Base).
+    fn all() -> Self;
+
+    /// Returns `true` if the set is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Returns the number of variants present in the set.
+    fn len(&self) -> usize;
+
+    /// Inserts a variant into the set.
+    fn insert<T: Borrow<Self::Base>>(&mut self, item: T);
+
+    /// Removes a variant from the set.
+    fn remove<T: Borrow<Self::Base>>(&mut self, item: T);
+
+    /// Returns `true` if the set contains the given variant.
+    fn contains<T: Borrow<Self::Base>>(&self, item: T) -> bool;
+
+    /// Creates a new set that contains all the variants that are in either `self` or `other`.
+    fn union(&self, other: &Self) -> Self;
+
+    /// Returns an iterator over the variants contained in the set.
+    fn iter(&self) -> Self::Iter;
+
+    /// Returns the integer representation of the set.
+    fn to_repr(&self) -> Self::Repr;
+
+    /// Returns the name of a variant.
+    fn name<T: Borrow<Self::Base>>(item: T) -> &'static str;
+
+    /// Returns the variant with the given name, if it is part of the set.
+    fn from_name(name: &str) -> Option<Self::Base>;
+
+    /// Creates a new set from its integer representation. Returns `None` if a bit that does not
+    /// correspond to any variant is set to 1.
+    fn from_repr(repr: Self::Repr) -> Option<Self>;
+}
+
+
+/// Implemented by every enum that derives [`EnumBitset`](derive@crate::EnumBitset), linking it to
+/// its generated set type.
+///
+/// This allows generic code to refer to the set type of an enum without knowing its (possibly
+/// customized) name, either as `E::Set` or using the [`Set`] alias.
+///
+/// ```rust
+/// use enum_bitset::{BitsetBase, EnumBitset, Set};
+///
+/// #[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+/// # #[bitset(serde = false)]
+/// #[bitset(name = Phases)]
+/// enum Phase {
+///     Setup,
+///     Run,
+///     Teardown,
+/// }
+///
+/// fn everything_but<E: BitsetBase>(item: E) -> Set<E> {
+///     let mut set = Set::<E>::all();
+///     set.remove(item);
+///     set
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Phase::COUNT, 3);
+/// assert_eq!(Phase::Run.index(), 1);
+/// assert_eq!(everything_but(Phase::Run), Phases::from([Phase::Setup, Phase::Teardown]));
+/// # }
+/// ```
+pub trait BitsetBase: Clone + 'static {
+    /// The set type generated for this enum.
+    type Set: EnumBitset<Base = Self>;
+
+    /// The number of variants of the enum.
+    const COUNT: usize;
+
+    /// The position of the variant in the declaration of the enum, starting at 0. Variants
+    /// excluded with `#[bitset(skip)]` are not counted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a variant excluded with `#[bitset(skip)]`.
+    fn index(&self) -> usize;
+
+    /// Returns the variant with the given [`index`](BitsetBase::index), or `None` if it is out of
+    /// range.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// An array with one element per variant, `[V; COUNT]`. Used as storage by
+    /// [`EnumMap`](crate::EnumMap).
+    #[doc(hidden)]
+    type Array<V>: AsRef<[V]> + AsMut<[V]>;
+
+    /// Creates a new [`Array`](BitsetBase::Array), initializing every element with the result of
+    /// calling `f` with its index.
+    #[doc(hidden)]
+    fn array_from_fn<V, F: FnMut(usize) -> V>(f: F) -> Self::Array<V>;
+}
+
+
+/// The set type generated for the enum `E`.
+pub type Set<E> = <E as BitsetBase>::Set;
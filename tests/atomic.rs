@@ -0,0 +1,120 @@
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, atomic)]
+enum State {
+    Config,
+    Storage,
+    Network,
+    Workers,
+}
+
+#[derive(EnumBitset, Clone, Debug, PartialEq)]
+#[bitset(serde = false, atomic, repr = u64)]
+enum Job {
+    Queued,
+    #[bitset(skip)]
+    Failed(String),
+    Running,
+    #[bitset(bit = 63)]
+    Done,
+}
+
+#[test]
+fn load_and_store() {
+    let atomic = AtomicStateSet::new(State::Config | State::Storage);
+    check!(atomic.load(Ordering::Relaxed) == State::Config | State::Storage);
+
+    atomic.store(StateSet::all(), Ordering::Relaxed);
+    check!(atomic.load(Ordering::Relaxed) == StateSet::all());
+    check!(atomic.into_inner() == StateSet::all());
+
+    check!(AtomicStateSet::default().load(Ordering::Relaxed) == StateSet::empty());
+    check!(
+        AtomicStateSet::from(State::Network.as_bitset()).into_inner() == State::Network.as_bitset()
+    );
+}
+
+#[test]
+fn fetch_operations() {
+    let atomic = AtomicStateSet::new(State::Config.as_bitset());
+
+    check!(atomic.fetch_insert(State::Storage, Ordering::Relaxed) == State::Config.as_bitset());
+    check!(
+        atomic.fetch_union(State::Network | State::Workers, Ordering::Relaxed)
+            == State::Config | State::Storage
+    );
+    check!(atomic.fetch_remove(State::Config, Ordering::Relaxed) == StateSet::all());
+    check!(atomic.load(Ordering::Relaxed) == State::Storage | State::Network | State::Workers);
+}
+
+#[test]
+fn insert_and_remove_report_changes() {
+    let atomic = AtomicStateSet::default();
+
+    check!(atomic.insert(State::Network, Ordering::Relaxed));
+    check!(!atomic.insert(State::Network, Ordering::Relaxed));
+    check!(atomic.remove(State::Network, Ordering::Relaxed));
+    check!(!atomic.remove(State::Network, Ordering::Relaxed));
+
+    let jobs = AtomicJobSet::default();
+    check!(!jobs.insert(Job::Failed("timeout".into()), Ordering::Relaxed));
+    check!(jobs.insert(Job::Done, Ordering::Relaxed));
+    check!(jobs.load(Ordering::Relaxed) == Job::Done.as_bitset());
+}
+
+#[test]
+fn compare_exchange() {
+    let atomic = AtomicStateSet::new(State::Config.as_bitset());
+
+    let result = atomic.compare_exchange(
+        StateSet::empty(),
+        StateSet::all(),
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    );
+    check!(result == Err(State::Config.as_bitset()));
+
+    let result = atomic.compare_exchange(
+        State::Config.as_bitset(),
+        StateSet::all(),
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    );
+    check!(result == Ok(State::Config.as_bitset()));
+    check!(atomic.load(Ordering::Relaxed) == StateSet::all());
+}
+
+#[test]
+fn shared_between_threads() {
+    let ready = AtomicJobSet::default();
+    let variants = [Job::Queued, Job::Running, Job::Done];
+
+    let newly_added: usize = thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let ready = &ready;
+                let variant = variants[thread % variants.len()].clone();
+                scope.spawn(move || ready.insert(variant, Ordering::AcqRel))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| usize::from(handle.join().unwrap()))
+            .sum()
+    });
+
+    check!(newly_added == 3);
+    check!(ready.load(Ordering::Acquire) == JobSet::all());
+}
+
+#[test]
+fn debug() {
+    let atomic = AtomicStateSet::new(State::Storage.as_bitset());
+    check!(format!("{atomic:?}") == "AtomicStateSet(StateSet(1){Storage})");
+}
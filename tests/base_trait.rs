@@ -0,0 +1,63 @@
+use assert2::check;
+use enum_bitset::{BitsetBase, EnumBitset, Set};
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum State {
+    Pending,
+    Running,
+    Done,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, name = Colors)]
+enum Color {
+    Red,
+    Green,
+}
+
+
+fn singleton<E: BitsetBase>(item: E) -> Set<E> {
+    let mut set = Set::<E>::empty();
+    set.insert(item);
+    set
+}
+
+fn full_len<E: BitsetBase>() -> usize {
+    E::Set::all().len()
+}
+
+
+#[test]
+fn count() {
+    check!(State::COUNT == 3);
+    check!(Color::COUNT == 2);
+}
+
+#[test]
+fn index() {
+    check!(State::Pending.index() == 0);
+    check!(State::Running.index() == 1);
+    check!(State::Done.index() == 2);
+    check!(Color::Red.index() == 0);
+    check!(Color::Green.index() == 1);
+}
+
+#[test]
+fn index_matches_variants() {
+    for (index, variant) in StateSet::VARIANTS.iter().enumerate() {
+        check!(variant.index() == index);
+    }
+}
+
+#[test]
+fn set_alias() {
+    check!(singleton(State::Running) == StateSet::from([State::Running]));
+    check!(singleton(Color::Green) == Colors::from([Color::Green]));
+}
+
+#[test]
+fn associated_set() {
+    check!(full_len::<State>() == 3);
+    check!(full_len::<Color>() == 2);
+}
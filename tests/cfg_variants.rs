@@ -0,0 +1,116 @@
+use assert2::check;
+use enum_bitset::{BitsetBase, EnumBitset, EnumMap};
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Backend {
+    Cpu,
+    #[cfg(not(test))]
+    Gpu,
+    Simd,
+    #[cfg(test)]
+    Threads,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum OnlyGated {
+    #[cfg(test)]
+    #[cfg(not(doc))]
+    Present,
+    #[cfg(not(test))]
+    Absent,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Pinned {
+    #[bitset(bit = 0)]
+    Cpu,
+    #[cfg(not(test))]
+    #[bitset(bit = 1)]
+    Gpu,
+    #[bitset(bit = 2)]
+    Simd,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Wide {
+    A,
+    #[cfg(not(test))]
+    B,
+    #[bitset(bit = 100)]
+    C,
+}
+
+// `GPU_ONLY` has no active variant, so it has no constant
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Accel {
+    Cpu,
+    #[cfg(not(test))]
+    #[bitset(group(gpu_only))]
+    Gpu,
+    #[bitset(group(fast))]
+    Simd,
+    #[cfg(not(test))]
+    #[bitset(group(fast))]
+    Cuda,
+}
+
+
+#[test]
+fn bits_follow_active_configuration() {
+    // `Gpu` is compiled out, so the following variants shift down one bit.
+    check!(BackendSet::MASK == 0b111);
+    check!(Backend::Simd.as_bitset().to_repr() == 0b10);
+    check!(Backend::Threads.as_bitset().to_repr() == 0b100);
+    check!(BackendSet::from_repr(0b1000) == None);
+
+    check!(OnlyGatedSet::MASK == 0b1);
+    check!(WideSet::MASK == 0b1 | (1 << 100));
+}
+
+#[test]
+fn pinned_bits_do_not_shift() {
+    check!(PinnedSet::MASK == 0b101);
+    check!(Pinned::Simd.as_bitset().to_repr() == 0b100);
+    check!(PinnedSet::from_repr(0b10) == None);
+}
+
+#[test]
+fn variants_follow_active_configuration() {
+    check!(BackendSet::VARIANTS == [Backend::Cpu, Backend::Simd, Backend::Threads]);
+    check!(<BackendSet as EnumBitset>::VARIANTS.len() == 3);
+    check!(BackendSet::all().len() == 3);
+    check!(OnlyGatedSet::VARIANTS == [OnlyGated::Present]);
+    check!(WideSet::VARIANTS == [Wide::A, Wide::C]);
+}
+
+#[test]
+fn iter() {
+    let items: Vec<_> = BackendSet::all().iter().collect();
+    check!(items == [Backend::Cpu, Backend::Simd, Backend::Threads]);
+
+    let items: Vec<_> = WideSet::all().iter().collect();
+    check!(items == [Wide::A, Wide::C]);
+}
+
+#[test]
+fn base_trait() {
+    check!(Backend::COUNT == 3);
+    check!(Backend::Cpu.index() == 0);
+    check!(Backend::Simd.index() == 1);
+    check!(Backend::Threads.index() == 2);
+
+    let map = EnumMap::from([(Backend::Threads, 4), (Backend::Cpu, 1)]);
+    check!(map.keys() == Backend::Cpu | Backend::Threads);
+    check!(map.iter().collect::<Vec<_>>() == [(Backend::Cpu, &1), (Backend::Threads, &4)]);
+}
+
+#[test]
+fn groups_only_contain_active_variants() {
+    check!(AccelSet::FAST == Accel::Simd.as_bitset());
+    check!(AccelSet::MASK == 0b11);
+}
@@ -0,0 +1,51 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, bits = "discriminant")]
+#[repr(u8)]
+enum Access {
+    Read = 0,
+    Write = 1,
+    Exec = 5,
+    // Implicitly takes discriminant (and bit) 6
+    Admin,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, bits = "discriminant", repr = u32)]
+enum Sparse {
+    Low = 3,
+    High = 31,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, bits = "discriminant")]
+enum Far {
+    Near,
+    Far = 130,
+}
+
+
+#[test]
+fn bits_match_discriminants() {
+    for item in AccessSet::VARIANTS {
+        check!(item.as_bitset().to_repr() == 1 << (item as u8));
+    }
+    check!(AccessSet::MASK == 0b110_0011);
+    check!(SparseSet::MASK == (1 << 31) | (1 << 3));
+    check!(FarSet::MASK == [1, 0, 1 << 2]);
+}
+
+#[test]
+fn repr_round_trip() {
+    check!(AccessSet::from_repr(0b10_0001) == Some(Access::Read | Access::Exec));
+    check!(AccessSet::from_repr(0b100) == None);
+    check!(SparseSet::from_repr(1 << 31) == Some(SparseSet::from([Sparse::High])));
+}
+
+#[test]
+fn iter_follows_bit_order() {
+    let items: Vec<_> = AccessSet::all().iter().collect();
+    check!(items == [Access::Read, Access::Write, Access::Exec, Access::Admin]);
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, display)]
+enum Feature {
+    Metrics,
+    #[bitset(bit = 5)]
+    Tracing,
+    #[bitset(bit = 2)]
+    Profiling,
+    #[bitset(skip)]
+    Unknown,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, display(sep = ","), name = Flags)]
+enum Flag {
+    Read,
+    Write,
+    Exec,
+}
+
+#[test]
+fn display() {
+    check!(FeatureSet::empty().to_string() == "");
+    check!(FeatureSet::from_array([Feature::Unknown]).to_string() == "");
+    check!(Feature::Metrics.as_bitset().to_string() == "Metrics");
+    check!(FeatureSet::all().to_string() == "Metrics | Profiling | Tracing");
+    check!((Flag::Exec | Flag::Read).to_string() == "Read,Exec");
+}
+
+#[test]
+fn parse() {
+    check!("".parse::<FeatureSet>() == Ok(FeatureSet::empty()));
+    check!("Tracing|Metrics".parse() == Ok(Feature::Tracing | Feature::Metrics));
+    check!(" Tracing  |  Tracing ".parse() == Ok(Feature::Tracing.as_bitset()));
+    check!(Flags::from_str(" Write , Exec ") == Ok(Flag::Write | Flag::Exec));
+
+    for set in [Flags::empty(), Flags::all(), Flag::Write.as_bitset()] {
+        check!(set.to_string().parse() == Ok(set));
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let_assert!(Err(error) = "Metrics | Unknown".parse::<FeatureSet>());
+    check!(error.token() == "Unknown");
+    check!(error.to_string() == "unknown variant `Unknown`");
+
+    let_assert!(Err(error) = Flags::from_str("Read,,Write"));
+    check!(error.token() == "");
+    check!(error.to_string() == "empty variant name");
+
+    let_assert!(Err(error) = Flags::from_str("Read | Write"));
+    check!(error.token() == "Read | Write");
+
+    let long = "x".repeat(100);
+    let_assert!(Err(error) = Flags::from_str(&long));
+    check!(error.is_truncated());
+    check!(error.token() == &long[..32]);
+    check!(error.to_string() == format!("unknown variant `{}...`", &long[..32]));
+}
@@ -0,0 +1,198 @@
+use assert2::check;
+use enum_bitset::{EnumBitset, EnumMap, map::Entry};
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Phase {
+    Setup,
+    Run,
+    Teardown,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum State {
+    Idle,
+    #[bitset(skip)]
+    Unknown,
+    Busy,
+}
+
+
+#[test]
+fn new_is_empty() {
+    let map = EnumMap::<Phase, u32>::new();
+
+    check!(map.is_empty());
+    check!(map.len() == 0);
+    check!(map.keys().is_empty());
+    check!(map.iter().next().is_none());
+    check!(map == EnumMap::default());
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = EnumMap::new();
+
+    check!(map.insert(Phase::Run, "run") == None);
+    check!(map.insert(Phase::Run, "running") == Some("run"));
+    check!(map.len() == 1);
+    check!(map.contains_key(&Phase::Run));
+    check!(!map.contains_key(&Phase::Setup));
+    check!(map.get(&Phase::Run) == Some(&"running"));
+    check!(map.get(&Phase::Teardown) == None);
+
+    check!(map.remove(&Phase::Run) == Some("running"));
+    check!(map.remove(&Phase::Run) == None);
+    check!(map.is_empty());
+}
+
+#[test]
+fn get_mut() {
+    let mut map = EnumMap::from([(Phase::Setup, 1)]);
+
+    *map.get_mut(&Phase::Setup).unwrap() += 41;
+    check!(map.get_mut(&Phase::Run).is_none());
+    check!(map[Phase::Setup] == 42);
+}
+
+#[test]
+fn keys() {
+    let map = EnumMap::from([(Phase::Teardown, ()), (Phase::Setup, ())]);
+
+    check!(map.keys() == PhaseSet::from([Phase::Setup, Phase::Teardown]));
+}
+
+#[test]
+fn iter_in_declaration_order() {
+    let map = EnumMap::from([(Phase::Teardown, 3), (Phase::Setup, 1), (Phase::Run, 2)]);
+
+    let entries: Vec<_> = map.iter().collect();
+    check!(entries == [(Phase::Setup, &1), (Phase::Run, &2), (Phase::Teardown, &3)]);
+
+    let values: Vec<_> = map.values().copied().collect();
+    check!(values == [1, 2, 3]);
+
+    let owned: Vec<_> = map.into_iter().collect();
+    check!(owned == [(Phase::Setup, 1), (Phase::Run, 2), (Phase::Teardown, 3)]);
+}
+
+#[test]
+fn iter_mut() {
+    let mut map = EnumMap::from([(Phase::Setup, 1), (Phase::Teardown, 3)]);
+
+    for (_, value) in &mut map {
+        *value *= 10;
+    }
+    map.values_mut().for_each(|value| *value += 1);
+
+    check!(map == EnumMap::from([(Phase::Setup, 11), (Phase::Teardown, 31)]));
+}
+
+#[test]
+fn from_fn() {
+    let map = EnumMap::from_fn(|phase: Phase| phase as usize * 2);
+
+    check!(map.len() == 3);
+    check!(map.keys().is_all());
+    check!(map[Phase::Teardown] == 4);
+}
+
+#[test]
+fn retain_and_clear() {
+    let mut map = EnumMap::from_fn(|phase: Phase| phase as usize);
+
+    map.retain(|phase, _| phase != Phase::Run);
+    check!(map.keys() == Phase::Setup | Phase::Teardown);
+
+    map.clear();
+    check!(map.is_empty());
+}
+
+#[test]
+fn entry() {
+    let mut map = EnumMap::new();
+
+    *map.entry(Phase::Run).or_insert(0) += 1;
+    *map.entry(Phase::Run).or_insert(0) += 1;
+    map.entry(Phase::Setup).or_default();
+    map.entry(Phase::Setup).and_modify(|value| *value = 7);
+    map.entry(Phase::Teardown).and_modify(|value| *value = 7);
+
+    check!(map == EnumMap::from([(Phase::Setup, 7), (Phase::Run, 2)]));
+
+    match map.entry(Phase::Setup) {
+        Entry::Occupied(entry) => {
+            check!(entry.remove() == 7);
+        }
+        Entry::Vacant(_) => panic!("Setup should be occupied"),
+    }
+
+    match map.entry(Phase::Teardown) {
+        Entry::Occupied(_) => panic!("Teardown should be vacant"),
+        Entry::Vacant(entry) => {
+            check!(*entry.key() == Phase::Teardown);
+            *entry.insert(5) += 1;
+        }
+    }
+
+    check!(map == EnumMap::from([(Phase::Run, 2), (Phase::Teardown, 6)]));
+}
+
+#[test]
+#[should_panic]
+fn index_missing_key() {
+    let map = EnumMap::<Phase, u8>::new();
+    let _ = map[Phase::Run];
+}
+
+#[test]
+fn debug() {
+    let map = EnumMap::from([(Phase::Teardown, 3), (Phase::Setup, 1)]);
+
+    check!(format!("{map:?}") == "{Setup: 1, Teardown: 3}");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+    let map = EnumMap::from([(Phase::Teardown, 3), (Phase::Setup, 1)]);
+
+    let json = serde_json::to_string(&map).unwrap();
+    check!(json == r#"{"Setup":1,"Teardown":3}"#);
+
+    let deserialized: EnumMap<Phase, i32> = serde_json::from_str(&json).unwrap();
+    check!(deserialized == map);
+}
+
+#[test]
+fn skipped_keys_are_never_found() {
+    let mut map = EnumMap::from([(State::Idle, 1), (State::Busy, 2)]);
+
+    check!(!map.contains_key(&State::Unknown));
+    check!(map.get(&State::Unknown) == None);
+    check!(map.get_mut(&State::Unknown) == None);
+    check!(map.remove(&State::Unknown) == None);
+    check!(map.len() == 2);
+}
+
+#[test]
+#[should_panic(expected = "`Unknown` is skipped, and cannot be a key of an EnumMap")]
+fn insert_skipped_key() {
+    EnumMap::new().insert(State::Unknown, 1);
+}
+
+#[test]
+#[should_panic(expected = "`Unknown` is skipped, and cannot be a key of an EnumMap")]
+fn entry_of_skipped_key() {
+    EnumMap::<State, u8>::new().entry(State::Unknown);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_skipped_key() {
+    let result = serde_json::from_str::<EnumMap<State, u8>>(r#"{"Idle":0,"Unknown":1}"#);
+
+    assert2::let_assert!(Err(error) = result);
+    check!(error.to_string().contains("`Unknown` is skipped, and cannot be a key of an EnumMap"));
+}
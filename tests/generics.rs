@@ -0,0 +1,147 @@
+use std::marker::PhantomData;
+
+use assert2::check;
+use enum_bitset::{BitsetBase, EnumBitset, EnumMap, Set};
+
+/// Marker type that implements none of the usual traits.
+struct Meters;
+
+#[derive(EnumBitset)]
+#[bitset(serde = false)]
+enum Kind<T> {
+    Length,
+    Area,
+    Volume,
+    #[bitset(skip)]
+    _Unit(PhantomData<T>),
+}
+
+// Implemented by hand, so that they do not require `T` to implement them.
+impl<T> Clone for Kind<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Kind::Length => Kind::Length,
+            Kind::Area => Kind::Area,
+            Kind::Volume => Kind::Volume,
+            Kind::_Unit(_) => Kind::_Unit(PhantomData),
+        }
+    }
+}
+
+impl<T> PartialEq for Kind<T> {
+    fn eq(&self, other: &Self) -> bool {
+        core::mem::discriminant(self) == core::mem::discriminant(other)
+    }
+}
+
+impl<T> core::fmt::Debug for Kind<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Kind::Length => f.write_str("Length"),
+            Kind::Area => f.write_str("Area"),
+            Kind::Volume => f.write_str("Volume"),
+            Kind::_Unit(_) => f.write_str("_Unit"),
+        }
+    }
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Token<'a> {
+    Open,
+    Close,
+    #[bitset(skip)]
+    Text(&'a str),
+}
+
+/// Uses the names of the generic parameters of the generated code.
+#[derive(EnumBitset, Clone, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Clash<'a, T: Default, I, const N: usize>
+where
+    I: Iterator<Item = T>,
+{
+    A,
+    B,
+    #[bitset(skip)]
+    _P(PhantomData<(&'a T, I, [u8; N])>),
+}
+
+
+#[test]
+fn generic_set() {
+    let mut set = KindSet::<Meters>::empty();
+    set.insert(Kind::Area);
+    set.insert(Kind::Volume);
+
+    check!(set.len() == 2);
+    check!(set.contains(Kind::Area));
+    check!(!set.contains(Kind::_Unit(PhantomData)));
+    check!(set.to_repr() == 0b110);
+    check!(set == Kind::Area | Kind::Volume);
+    check!(set.complement() == KindSet::from([Kind::Length]));
+
+    let items: Vec<Kind<Meters>> = set.iter().collect();
+    check!(items == [Kind::Area, Kind::Volume]);
+    check!(format!("{set:?}") == "KindSet(2){Area, Volume}");
+}
+
+#[test]
+fn generic_traits() {
+    fn all<E: BitsetBase>() -> Set<E> {
+        Set::<E>::all()
+    }
+
+    check!(all::<Kind<Meters>>().len() == 3);
+    check!(Kind::<Meters>::COUNT == 3);
+    check!(Kind::<Meters>::Volume.index() == 2);
+    check!(<KindSet<Meters> as EnumBitset>::VARIANTS.len() == 3);
+
+    let map = EnumMap::from([(Kind::<Meters>::Area, 10)]);
+    check!(map[Kind::Area] == 10);
+}
+
+#[test]
+fn lifetime_set() {
+    let text = String::from("hello");
+    let tokens = [Token::Open, Token::Text(&text), Token::Close];
+
+    let set: TokenSet = tokens.iter().collect();
+    check!(set == TokenSet::all());
+    check!(TokenSet::VARIANTS == [Token::Open, Token::Close]);
+}
+
+#[test]
+fn clashing_names() {
+    type C = Clash<'static, u8, std::iter::Empty<u8>, 3>;
+
+    let set: ClashSet<'static, u8, std::iter::Empty<u8>, 3> = [C::A].into_iter().collect();
+    check!(set.contains(C::A));
+    check!(set.iter().count() == 1);
+    check!(set.collect::<Vec<_>>().len() == 1);
+    check!(C::COUNT == 2);
+}
+
+
+#[cfg(feature = "serde")]
+mod serde {
+    use assert2::check;
+    use enum_bitset::EnumBitset;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(EnumBitset, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+    enum Wrapper<T> {
+        Empty,
+        Full,
+        #[bitset(skip)]
+        Value(T),
+    }
+
+    #[test]
+    fn round_trip() {
+        let set = WrapperSet::<u32>::from([Wrapper::Full]);
+        let json = serde_json::to_string(&set).unwrap();
+        check!(json == r#"["Full"]"#);
+        check!(serde_json::from_str::<WrapperSet<u32>>(&json).unwrap() == set);
+    }
+}
@@ -0,0 +1,75 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false)]
+enum Permission {
+    #[bitset(group(read_only, admin))]
+    Read,
+    #[bitset(group(admin))]
+    Write,
+    #[bitset(group(admin, billing))]
+    Invoice,
+    #[bitset(group(billing), group(read_only))]
+    ViewInvoices,
+    #[cfg(test)]
+    #[bitset(group(debug))]
+    Trace,
+    Login,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, repr = [u64; 2])]
+enum Wide {
+    #[bitset(group(edges))]
+    First,
+    #[bitset(bit = 100, group(edges, far))]
+    Last,
+}
+
+
+// `all` and `empty` are methods of the set, so groups can be named like them
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false)]
+enum Alert {
+    #[bitset(group(all))]
+    Page,
+    #[bitset(group(all, empty))]
+    Email,
+    Log,
+}
+
+
+#[test]
+fn group_constants() {
+    check!(PermissionSet::READ_ONLY == Permission::Read | Permission::ViewInvoices);
+    check!(PermissionSet::ADMIN == Permission::Read | Permission::Write | Permission::Invoice);
+    check!(PermissionSet::BILLING == Permission::Invoice | Permission::ViewInvoices);
+    check!(PermissionSet::DEBUG == Permission::Trace.as_bitset());
+    check!(!PermissionSet::ADMIN.contains(Permission::Login));
+}
+
+
+#[test]
+fn const_context() {
+    const SUPPORT: PermissionSet = PermissionSet::READ_ONLY.union(&PermissionSet::BILLING);
+
+    check!(SUPPORT.len() == 3);
+    check!(SUPPORT.is_superset_of(&PermissionSet::BILLING));
+}
+
+
+#[test]
+fn word_groups() {
+    check!(WideSet::EDGES == WideSet::all());
+    check!(WideSet::FAR.to_repr() == [0, 1 << 36]);
+}
+
+
+#[test]
+fn method_names() {
+    check!(AlertSet::ALL == Alert::Page | Alert::Email);
+    check!(AlertSet::all().len() == 3);
+    check!(AlertSet::EMPTY == Alert::Email.as_bitset());
+    check!(AlertSet::empty().is_empty());
+}
@@ -0,0 +1,177 @@
+use std::iter::FusedIterator;
+
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[bitset(serde = false)]
+enum Priority {
+    Low,
+    Normal,
+    High,
+    Critical,
+}
+
+// Bits out of declaration order
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[bitset(serde = false)]
+enum Flag {
+    #[bitset(bit = 4)]
+    Archived,
+    #[bitset(bit = 0)]
+    Hidden,
+    Pinned,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[bitset(serde = false)]
+enum Wide {
+    A,
+    #[bitset(bit = 70)]
+    B,
+    #[bitset(bit = 130)]
+    C,
+}
+
+// `Ord` follows the discriminants, not the bits
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[bitset(serde = false)]
+enum Prio {
+    High = 10,
+    Low = 1,
+    Mid = 5,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Marked<T> {
+    A,
+    B,
+    C,
+    #[bitset(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+fn assert_traits<
+    I: DoubleEndedIterator + ExactSizeIterator + FusedIterator + Clone + std::fmt::Debug,
+>(
+    _: &I,
+) {
+}
+
+#[test]
+fn double_ended() {
+    let set = PrioritySet::all();
+
+    check!(
+        set.iter().rev().collect::<Vec<_>>()
+            == [
+                Priority::Critical,
+                Priority::High,
+                Priority::Normal,
+                Priority::Low
+            ]
+    );
+
+    let mut iter = set.iter();
+    check!(iter.next() == Some(Priority::Low));
+    check!(iter.next_back() == Some(Priority::Critical));
+    check!(iter.len() == 2);
+    check!(iter.next_back() == Some(Priority::High));
+    check!(iter.next() == Some(Priority::Normal));
+    check!(iter.next() == None);
+    check!(iter.next_back() == None);
+    check!(iter.len() == 0);
+
+    check!(WideSet::all().iter().rev().collect::<Vec<_>>() == [Wide::C, Wide::B, Wide::A]);
+    check!(
+        FlagSet::all().iter().rev().collect::<Vec<_>>()
+            == [Flag::Archived, Flag::Pinned, Flag::Hidden]
+    );
+}
+
+#[test]
+fn shortcuts() {
+    let set = Priority::Low | Priority::High | Priority::Critical;
+
+    check!(set.iter().last() == Some(Priority::Critical));
+    check!(set.iter().min() == Some(Priority::Low));
+    check!(set.iter().max() == Some(Priority::Critical));
+    check!(PrioritySet::empty().iter().last() == None);
+    check!(PrioritySet::empty().iter().max() == None);
+
+    let mut iter = set.iter();
+    check!(iter.nth(1) == Some(Priority::High));
+    check!(iter.next() == Some(Priority::Critical));
+    check!(iter.nth(1) == None);
+    check!(set.iter().nth(3) == None);
+    check!(set.iter().nth(usize::MAX) == None);
+    check!(set.iter().nth_back(1) == Some(Priority::High));
+
+    let wide = WideSet::all();
+    check!(wide.iter().nth(2) == Some(Wide::C));
+    check!(wide.iter().last() == Some(Wide::C));
+    check!(wide.iter().max() == Some(Wide::C));
+
+    // Iterated in bit order, but compared with `Ord`
+    let flags = FlagSet::all();
+    check!(flags.iter().next() == Some(Flag::Hidden));
+    check!(flags.iter().min() == Some(Flag::Archived));
+    check!(flags.iter().max() == Some(Flag::Pinned));
+    check!(flags.iter().last() == Some(Flag::Archived));
+
+    let prios = PrioSet::all();
+    check!(prios.iter().next() == Some(Prio::High));
+    check!(prios.iter().min() == Some(Prio::Low));
+    check!(prios.iter().max() == Some(Prio::High));
+    check!((Prio::Low | Prio::Mid).iter().max() == Some(Prio::Mid));
+}
+
+#[test]
+fn nth_back() {
+    let set = Priority::Low | Priority::Normal | Priority::High | Priority::Critical;
+
+    let mut iter = set.iter();
+    check!(iter.nth_back(0) == Some(Priority::Critical));
+    check!(iter.nth_back(1) == Some(Priority::Normal));
+    check!(iter.len() == 1);
+    check!(iter.nth_back(1) == None);
+    check!(iter.next() == None);
+    check!(set.iter().nth_back(usize::MAX) == None);
+
+    let wide = WideSet::all();
+    check!(wide.iter().nth_back(1) == Some(Wide::B));
+    check!(wide.iter().nth_back(2) == Some(Wide::A));
+    check!(wide.iter().nth_back(3) == None);
+}
+
+#[test]
+fn traits() {
+    let set = Priority::Normal | Priority::High;
+    assert_traits(&set.iter());
+    assert_traits(&MarkedSet::<String>::all().iter());
+
+    let mut iter = set.iter();
+    iter.by_ref().for_each(drop);
+    check!(iter.next() == None);
+    check!(iter.next() == None);
+
+    check!(format!("{:?}", set.iter()) == "PrioritySetSetIter(PrioritySet(2){Normal, High})");
+
+    let mut iter = MarkedSet::<u8>::all().iter();
+    iter.next();
+    check!(format!("{iter:?}") == "MarkedSetSetIter(MarkedSet(2){B, C})");
+}
+
+#[test]
+fn by_reference() {
+    let set = Priority::Low | Priority::Critical;
+    let mut seen = Vec::new();
+
+    for priority in &set {
+        seen.push(priority);
+    }
+
+    check!(seen == [Priority::Low, Priority::Critical]);
+    check!((&MarkedSet::<u8>::all()).into_iter().count() == 3);
+}
@@ -0,0 +1,110 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Debug, Clone, PartialEq)]
+#[bitset(kinds)]
+enum Event {
+    Click { x: i32, y: i32 },
+    Key(char),
+    Resize(u32, u32),
+    Focus,
+}
+
+#[cfg(feature = "serde")]
+#[derive(EnumBitset, Debug, PartialEq)]
+#[bitset(kinds, serde = "string", rename_all = "lowercase")]
+enum Command {
+    Open(String),
+    Close,
+}
+
+#[derive(EnumBitset, Debug)]
+#[bitset(kinds = Shape, name = Shapes, repr = u16)]
+enum Geometry<'a, T> {
+    Point(T),
+    Line(T, T),
+    #[bitset(bit = 10)]
+    Polygon(&'a [T]),
+    #[bitset(skip)]
+    Empty,
+}
+
+
+#[test]
+fn kind_of_value() {
+    check!(Event::Click { x: 1, y: 2 }.kind() == EventKind::Click);
+    check!(Event::Key('a').kind() == EventKind::Key);
+    check!(EventKind::from(&Event::Focus) == EventKind::Focus);
+    check!(
+        EventKindSet::VARIANTS
+            == [
+                EventKind::Click,
+                EventKind::Key,
+                EventKind::Resize,
+                EventKind::Focus
+            ]
+    );
+}
+
+#[test]
+fn filter_by_kind() {
+    let events = [
+        Event::Click { x: 0, y: 0 },
+        Event::Key('q'),
+        Event::Resize(640, 480),
+        Event::Key('w'),
+    ];
+
+    let wanted = EventKind::Key | EventKind::Focus;
+    let keys: Vec<_> = events
+        .iter()
+        .filter(|event| wanted.contains_kind_of(event))
+        .collect();
+    check!(keys == [&Event::Key('q'), &Event::Key('w')]);
+
+    let keys: Vec<_> = events
+        .iter()
+        .filter(|event| wanted.contains(*event))
+        .collect();
+    check!(keys == [&Event::Key('q'), &Event::Key('w')]);
+    check!(wanted.contains(&Event::Focus));
+    check!(wanted.contains(EventKind::Key));
+    check!(enum_bitset::EnumBitset::contains(&wanted, &EventKind::Key));
+    check!(!wanted.contains(&Event::Click { x: 1, y: 1 }));
+
+    let seen: EventKindSet = events.iter().map(Event::kind).collect();
+    check!(seen == EventKind::Click | EventKind::Key | EventKind::Resize);
+    check!(<EventKindSet as From<_>>::from(&Event::Focus) == EventKind::Focus.as_bitset());
+}
+
+#[test]
+fn configured_kinds() {
+    let points = [1, 2, 3];
+    let polygon = Geometry::Polygon(&points);
+
+    check!(polygon.kind() == Shape::Polygon);
+    let shapes: Shapes = (&polygon).into();
+    check!(shapes.to_repr() == 1 << 10);
+    check!(Shapes::MASK == 0b100_0000_0011);
+    check!(Shapes::from_iter([Geometry::<u8>::Empty.kind()]).is_empty());
+    check!(size_of::<Shapes>() == size_of::<u16>());
+    check!(Shapes::all().contains_kind_of(&Geometry::Line(1, 2)));
+    check!(!(Shape::Line | Shape::Polygon).contains_kind_of(&Geometry::Point(0)));
+    check!((Shape::Line | Shape::Polygon).contains(&polygon));
+    check!(!Shapes::all().contains(&Geometry::<u8>::Empty));
+
+    let Geometry::Polygon(slice) = polygon else {
+        unreachable!()
+    };
+    check!(slice == [1, 2, 3]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialized_kinds() {
+    let set = CommandKind::Open | CommandKind::Close;
+    check!(serde_json::to_string(&set).unwrap() == r#""open,close""#);
+    check!(serde_json::from_str::<CommandKindSet>(r#""close""#).unwrap() == CommandKind::Close.as_bitset());
+    check!(set.contains(&Command::Open("file".into())));
+    check!(Command::Close.kind() == CommandKind::Close);
+}
@@ -0,0 +1,258 @@
+use std::alloc::Layout;
+
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum EventType {
+    E000,
+    E001,
+    E002,
+    E003,
+    E004,
+    E005,
+    E006,
+    E007,
+    E008,
+    E009,
+    E010,
+    E011,
+    E012,
+    E013,
+    E014,
+    E015,
+    E016,
+    E017,
+    E018,
+    E019,
+    E020,
+    E021,
+    E022,
+    E023,
+    E024,
+    E025,
+    E026,
+    E027,
+    E028,
+    E029,
+    E030,
+    E031,
+    E032,
+    E033,
+    E034,
+    E035,
+    E036,
+    E037,
+    E038,
+    E039,
+    E040,
+    E041,
+    E042,
+    E043,
+    E044,
+    E045,
+    E046,
+    E047,
+    E048,
+    E049,
+    E050,
+    E051,
+    E052,
+    E053,
+    E054,
+    E055,
+    E056,
+    E057,
+    E058,
+    E059,
+    E060,
+    E061,
+    E062,
+    E063,
+    E064,
+    E065,
+    E066,
+    E067,
+    E068,
+    E069,
+    E070,
+    E071,
+    E072,
+    E073,
+    E074,
+    E075,
+    E076,
+    E077,
+    E078,
+    E079,
+    E080,
+    E081,
+    E082,
+    E083,
+    E084,
+    E085,
+    E086,
+    E087,
+    E088,
+    E089,
+    E090,
+    E091,
+    E092,
+    E093,
+    E094,
+    E095,
+    E096,
+    E097,
+    E098,
+    E099,
+    E100,
+    E101,
+    E102,
+    E103,
+    E104,
+    E105,
+    E106,
+    E107,
+    E108,
+    E109,
+    E110,
+    E111,
+    E112,
+    E113,
+    E114,
+    E115,
+    E116,
+    E117,
+    E118,
+    E119,
+    E120,
+    E121,
+    E122,
+    E123,
+    E124,
+    E125,
+    E126,
+    E127,
+    E128,
+    E129,
+    E130,
+    E131,
+    E132,
+    E133,
+    E134,
+    E135,
+    E136,
+    E137,
+    E138,
+    E139,
+    E140,
+    E141,
+    E142,
+    E143,
+    E144,
+    E145,
+    E146,
+    E147,
+    E148,
+    E149,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, repr = [u64; 4])]
+enum Small {
+    A,
+    B,
+    C,
+}
+
+
+#[test]
+fn layout() {
+    check!(Layout::new::<EventTypeSet>() == Layout::new::<[u64; 3]>());
+    check!(Layout::new::<SmallSet>() == Layout::new::<[u64; 4]>());
+}
+
+#[test]
+fn mask() {
+    check!(EventTypeSet::MASK == [u64::MAX, u64::MAX, 0x3f_ffff]);
+    check!(SmallSet::MASK == [0b111, 0, 0, 0]);
+}
+
+#[test]
+fn all_and_empty() {
+    check!(EventTypeSet::all().len() == 150);
+    check!(EventTypeSet::all().is_all());
+    check!(EventTypeSet::empty().is_empty());
+    check!(EventTypeSet::all().complement() == EventTypeSet::empty());
+    check!(SmallSet::all().len() == 3);
+}
+
+#[test]
+fn insert_contains_remove() {
+    let mut set = EventTypeSet::new();
+    set.insert(EventType::E000);
+    set.insert(EventType::E064);
+    set.insert(EventType::E149);
+
+    check!(set.len() == 3);
+    check!(set.contains(EventType::E000));
+    check!(set.contains(EventType::E064));
+    check!(set.contains(EventType::E149));
+    check!(!set.contains(EventType::E063));
+    check!(!set.contains(EventType::E128));
+
+    set.remove(EventType::E064);
+    check!(set.len() == 2);
+    check!(!set.contains(EventType::E064));
+}
+
+#[test]
+fn iter() {
+    let set = EventTypeSet::from([EventType::E149, EventType::E003, EventType::E100, EventType::E064]);
+    let items: Vec<_> = set.iter().collect();
+
+    check!(items == [EventType::E003, EventType::E064, EventType::E100, EventType::E149]);
+    check!(set.iter().count() == 4);
+    check!(EventTypeSet::all().iter().count() == 150);
+    check!(EventTypeSet::all().iter().last() == Some(EventType::E149));
+}
+
+#[test]
+fn set_ops() {
+    let a = EventType::E001 | EventType::E070 | EventType::E140;
+    let b = EventType::E070 | EventType::E141;
+
+    check!(a.union(&b) == EventTypeSet::from([EventType::E001, EventType::E070, EventType::E140, EventType::E141]));
+    check!(a.intersection(&b) == EventType::E070.into());
+    check!(a.difference(&b) == EventType::E001 | EventType::E140);
+    check!(a.symmetric_difference(&b) == EventTypeSet::from([EventType::E001, EventType::E140, EventType::E141]));
+    check!((a - b) == a.difference(&b));
+    check!((a ^ b) == a.symmetric_difference(&b));
+    check!((!a).len() == 147);
+    check!(a.is_superset_of(&EventType::E140.into()));
+    check!(EventTypeSet::from([EventType::E140]).is_subset_of(&a));
+    check!(a.complement().is_complementary(&a));
+}
+
+#[test]
+fn repr() {
+    let set = EventType::E000 | EventType::E065 | EventType::E129;
+
+    check!(set.to_repr() == [1, 2, 2]);
+    check!(EventTypeSet::from_repr([1, 2, 2]) == Some(set));
+    check!(EventTypeSet::from_repr([0, 0, 1 << 22]) == None);
+    check!(EventTypeSet::is_valid_repr([u64::MAX, u64::MAX, 0x3f_ffff]));
+    check!(!EventTypeSet::is_valid_repr([0, 0, u64::MAX]));
+
+    check!(SmallSet::from_repr([0b101, 0, 0, 0]) == Some(Small::A | Small::C));
+    check!(SmallSet::from_repr([0, 1, 0, 0]) == None);
+}
+
+#[test]
+fn from_slice_const() {
+    const SET: EventTypeSet = EventTypeSet::from_slice(&[EventType::E010, EventType::E130]);
+
+    check!(SET.len() == 2);
+    check!(SET.contains(EventType::E130));
+}
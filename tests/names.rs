@@ -0,0 +1,58 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false)]
+enum Excuse {
+    WorksOnMyMachine,
+    #[bitset(bit = 4)]
+    CacheIssue,
+    #[bitset(skip)]
+    Unknown,
+    #[cfg(test)]
+    CosmicRays,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, rename_all = "kebab-case", display(sep = ","))]
+enum Feature {
+    HttpServer,
+    #[bitset(rename = "otel")]
+    OpenTelemetry,
+    #[bitset(skip, rename = "???")]
+    Unknown(u32),
+    GrpcClient,
+}
+
+#[test]
+fn names() {
+    check!(ExcuseSet::NAMES == ["WorksOnMyMachine", "CacheIssue", "CosmicRays"]);
+    check!(ExcuseSet::name(Excuse::CacheIssue) == "CacheIssue");
+    check!(ExcuseSet::name(Excuse::Unknown) == "Unknown");
+    check!(ExcuseSet::from_name("CosmicRays") == Some(Excuse::CosmicRays));
+    check!(ExcuseSet::from_name("Unknown") == None);
+    check!(ExcuseSet::from_name("cacheissue") == None);
+}
+
+#[test]
+fn renamed() {
+    check!(FeatureSet::NAMES == ["http-server", "otel", "grpc-client"]);
+    check!(FeatureSet::name(Feature::Unknown(3)) == "???");
+    check!(FeatureSet::from_name("otel") == Some(Feature::OpenTelemetry));
+    check!(FeatureSet::from_name("OpenTelemetry") == None);
+
+    let set = Feature::GrpcClient | Feature::OpenTelemetry;
+    check!(set.to_string() == "otel,grpc-client");
+    check!("grpc-client, otel".parse() == Ok(set));
+}
+
+#[test]
+fn through_the_trait() {
+    fn describe<S: EnumBitset>(set: S) -> Vec<&'static str> {
+        set.iter().map(S::name).collect()
+    }
+
+    check!(describe(FeatureSet::all()) == FeatureSet::NAMES);
+    check!(<FeatureSet as EnumBitset>::from_name("http-server") == Some(Feature::HttpServer));
+    check!(<ExcuseSet as EnumBitset>::NAMES.len() == ExcuseSet::VARIANTS.len());
+}
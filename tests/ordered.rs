@@ -0,0 +1,140 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Phase {
+    Setup,
+    Build,
+    Test,
+    Deploy,
+    Cleanup,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Wide {
+    A,
+    #[bitset(bit = 70)]
+    B,
+    #[bitset(bit = 130)]
+    C,
+}
+
+#[derive(EnumBitset, Clone, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Job {
+    Queued,
+    Running,
+    #[bitset(skip)]
+    Failed(String),
+    Done,
+}
+
+#[test]
+fn first_and_last() {
+    let set = Phase::Build | Phase::Deploy | Phase::Test;
+    check!(set.first() == Some(Phase::Build));
+    check!(set.last() == Some(Phase::Deploy));
+    check!(PhaseSet::empty().first() == None);
+    check!(PhaseSet::empty().last() == None);
+
+    check!(WideSet::all().last() == Some(Wide::C));
+    check!(Wide::B.as_bitset().first() == Some(Wide::B));
+}
+
+#[test]
+fn pop() {
+    let mut pending = Phase::Setup | Phase::Test | Phase::Cleanup;
+
+    check!(pending.pop_first() == Some(Phase::Setup));
+    check!(pending.pop_last() == Some(Phase::Cleanup));
+    check!(pending == Phase::Test.as_bitset());
+    check!(pending.pop_last() == Some(Phase::Test));
+    check!(pending.pop_first() == None);
+    check!(pending.is_empty());
+
+    let mut wide = WideSet::all();
+    check!(wide.pop_last() == Some(Wide::C));
+    check!(wide.pop_last() == Some(Wide::B));
+    check!(wide == Wide::A.as_bitset());
+}
+
+#[test]
+fn neighbours() {
+    let set = Phase::Setup | Phase::Test | Phase::Cleanup;
+
+    check!(set.next_after(Phase::Setup) == Some(Phase::Test));
+    check!(set.next_after(Phase::Build) == Some(Phase::Test));
+    check!(set.next_after(Phase::Test) == Some(Phase::Cleanup));
+    check!(set.next_after(Phase::Cleanup) == None);
+
+    check!(set.prev_before(Phase::Cleanup) == Some(Phase::Test));
+    check!(set.prev_before(Phase::Deploy) == Some(Phase::Test));
+    check!(set.prev_before(Phase::Setup) == None);
+
+    check!(WideSet::all().next_after(Wide::A) == Some(Wide::B));
+    check!(WideSet::all().prev_before(Wide::C) == Some(Wide::B));
+
+    let jobs = JobSet::all();
+    check!(jobs.next_after(Job::Running) == Some(Job::Done));
+    check!(jobs.next_after(Job::Failed("oops".into())) == None);
+    check!(jobs.prev_before(Job::Failed("oops".into())) == None);
+}
+
+#[test]
+fn range() {
+    let all = PhaseSet::all();
+
+    check!(all.range(Phase::Test..) == Phase::Test | Phase::Deploy | Phase::Cleanup);
+    check!(all.range(..Phase::Test) == Phase::Setup | Phase::Build);
+    check!(all.range(..=Phase::Test) == Phase::Setup | Phase::Build | Phase::Test);
+    check!(all.range(Phase::Build..=Phase::Deploy) == Phase::Build | Phase::Test | Phase::Deploy);
+    check!(all.range(Phase::Build..Phase::Deploy) == Phase::Build | Phase::Test);
+    check!(all.range(Phase::Test..Phase::Test) == PhaseSet::empty());
+    check!(all.range(Phase::Deploy..=Phase::Build) == PhaseSet::empty());
+    check!(all.range(..) == all);
+
+    let set = Phase::Setup | Phase::Deploy;
+    check!(set.range(Phase::Build..) == Phase::Deploy.as_bitset());
+
+    use std::ops::Bound;
+    check!(
+        all.range((Bound::Excluded(Phase::Build), Bound::Unbounded))
+            == Phase::Test | Phase::Deploy | Phase::Cleanup
+    );
+
+    check!(WideSet::all().range(Wide::B..) == Wide::B | Wide::C);
+    check!(WideSet::all().range(..=Wide::B) == Wide::A | Wide::B);
+
+    check!(JobSet::all().range(Job::Running..) == Job::Running | Job::Done);
+    check!(JobSet::all().range(Job::Failed("oops".into())..) == JobSet::empty());
+}
+
+#[test]
+fn const_queries() {
+    const PENDING: PhaseSet = PhaseSet::from_array([Phase::Build, Phase::Test, Phase::Cleanup]);
+    const FIRST: Option<Phase> = PENDING.first();
+    const LAST: Option<Phase> = PENDING.last();
+    const SECOND: Option<Phase> = PENDING.select(1);
+    const REST: PhaseSet = {
+        let mut rest = PENDING;
+        rest.pop_first();
+        rest.pop_last();
+        rest
+    };
+
+    check!(FIRST == Some(Phase::Build));
+    check!(LAST == Some(Phase::Cleanup));
+    check!(SECOND == Some(Phase::Test));
+    check!(REST == Phase::Test.as_bitset());
+    check!(PENDING.select(3) == None);
+
+    const WIDE_LAST: Option<Wide> = WideSet::all().last();
+    const WIDE_SECOND: Option<Wide> = WideSet::all().select(1);
+    check!(WIDE_LAST == Some(Wide::C));
+    check!(WIDE_SECOND == Some(Wide::B));
+
+    const LAST_JOB: Option<Job> = JobSet::all().last();
+    check!(LAST_JOB == Some(Job::Done));
+}
@@ -0,0 +1,81 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Flag {
+    #[bitset(bit = 4)]
+    Archived,
+    #[bitset(bit = 0)]
+    Hidden,
+    // Implicitly takes the bit after `Hidden`
+    Pinned,
+    #[bitset(bit = 7)]
+    System,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, repr = u16)]
+enum Wide {
+    A,
+    #[bitset(bit = 15)]
+    B,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Auto {
+    A,
+    #[bitset(bit = 200)]
+    B,
+}
+
+
+#[test]
+fn mask() {
+    check!(FlagSet::MASK == 0b1001_0011);
+    check!(WideSet::MASK == 0x8001);
+    check!(AutoSet::MASK == [1, 0, 0, 1 << 8]);
+}
+
+#[test]
+fn to_repr() {
+    check!(FlagSet::from([Flag::Archived]).to_repr() == 0b1_0000);
+    check!(FlagSet::from([Flag::Hidden]).to_repr() == 0b1);
+    check!(FlagSet::from([Flag::Pinned]).to_repr() == 0b10);
+    check!(FlagSet::from([Flag::System]).to_repr() == 0b1000_0000);
+    check!(WideSet::from([Wide::B]).to_repr() == 1 << 15);
+    check!(AutoSet::from([Auto::B]).to_repr() == [0, 0, 0, 1 << 8]);
+}
+
+#[test]
+fn from_repr() {
+    check!(FlagSet::from_repr(0b1001_0000) == Some(Flag::Archived | Flag::System));
+    check!(FlagSet::from_repr(0b100) == None);
+    check!(FlagSet::from_repr(0b1000) == None);
+    check!(FlagSet::from_repr(FlagSet::MASK) == Some(FlagSet::all()));
+    check!(!FlagSet::is_valid_repr(0b0100_0000));
+}
+
+#[test]
+fn all_and_complement() {
+    check!(FlagSet::all().len() == 4);
+    check!(FlagSet::from([Flag::Hidden]).complement() == FlagSet::from([Flag::Archived, Flag::Pinned, Flag::System]));
+    check!((!FlagSet::empty()).is_all());
+}
+
+#[test]
+fn iter_follows_bit_order() {
+    let items: Vec<_> = FlagSet::all().iter().collect();
+    check!(items == [Flag::Hidden, Flag::Pinned, Flag::Archived, Flag::System]);
+
+    let items: Vec<_> = AutoSet::all().iter().collect();
+    check!(items == [Auto::A, Auto::B]);
+}
+
+#[test]
+fn variants_and_index_follow_declaration_order() {
+    check!(FlagSet::VARIANTS == [Flag::Archived, Flag::Hidden, Flag::Pinned, Flag::System]);
+    check!(Flag::Archived.index() == 0);
+    check!(Flag::System.index() == 3);
+}
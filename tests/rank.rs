@@ -0,0 +1,130 @@
+use assert2::check;
+use enum_bitset::{BitsetBase, EnumBitset};
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Column {
+    Name,
+    Size,
+    Owner,
+    Modified,
+}
+
+#[derive(EnumBitset, Clone, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Job {
+    Queued,
+    #[bitset(skip)]
+    Failed(String),
+    Running,
+    #[cfg(not(test))]
+    Paused,
+    Done,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Wide {
+    A,
+    #[bitset(bit = 70)]
+    B,
+    #[bitset(bit = 130)]
+    C,
+}
+
+// Already has an `index` method
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, no_base_index)]
+enum Page {
+    Cover,
+    Contents,
+    Back,
+}
+
+impl Page {
+    fn index(&self) -> &'static str {
+        "contents"
+    }
+}
+
+const OWNER: usize = Column::Owner.index();
+const LAST: Option<Column> = Column::from_index(3);
+
+#[test]
+fn index() {
+    check!(Column::Name.index() == 0);
+    check!(OWNER == 2);
+    check!(<Column as BitsetBase>::index(&Column::Modified) == 3);
+
+    check!(Job::Queued.index() == 0);
+    check!(Job::Running.index() == 1);
+    check!(Job::Done.index() == 2);
+
+    check!(Wide::C.index() == 2);
+}
+
+#[test]
+#[should_panic(expected = "`Job::Failed` is skipped, and has no index")]
+fn index_of_skipped() {
+    Job::Failed(String::new()).index();
+}
+
+#[test]
+fn from_index() {
+    check!(LAST == Some(Column::Modified));
+    check!(Column::from_index(4) == None);
+    check!(<Column as BitsetBase>::from_index(1) == Some(Column::Size));
+
+    check!(Job::from_index(1) == Some(Job::Running));
+    check!(Job::from_index(2) == Some(Job::Done));
+    check!(Job::from_index(3) == None);
+
+    for (index, variant) in WideSet::VARIANTS.iter().enumerate() {
+        check!(Wide::from_index(index).as_ref() == Some(variant));
+        check!(variant.index() == index);
+    }
+}
+
+#[test]
+fn rank() {
+    let visible = Column::Name | Column::Owner | Column::Modified;
+    check!(visible.rank(Column::Name) == 0);
+    check!(visible.rank(Column::Size) == 1);
+    check!(visible.rank(Column::Owner) == 1);
+    check!(visible.rank(Column::Modified) == 2);
+    check!(ColumnSet::empty().rank(Column::Modified) == 0);
+
+    check!(WideSet::all().rank(Wide::C) == 2);
+    check!((Job::Queued | Job::Done).rank(Job::Done) == 1);
+}
+
+#[test]
+#[should_panic(expected = "skipped variants of `Job` have no rank")]
+fn rank_of_skipped() {
+    JobSet::all().rank(Job::Failed(String::new()));
+}
+
+#[test]
+fn select() {
+    let visible = Column::Name | Column::Owner | Column::Modified;
+    check!(visible.select(0) == Some(Column::Name));
+    check!(visible.select(1) == Some(Column::Owner));
+    check!(visible.select(2) == Some(Column::Modified));
+    check!(visible.select(3) == None);
+    check!(visible.select(usize::MAX) == None);
+
+    check!(WideSet::all().select(1) == Some(Wide::B));
+
+    for (position, column) in visible.iter().enumerate() {
+        check!(visible.rank(column) == position);
+        check!(visible.select(position) == Some(column));
+    }
+}
+
+#[test]
+fn no_base_index() {
+    check!(Page::Cover.index() == "contents");
+    check!(BitsetBase::index(&Page::Back) == 2);
+    check!(<Page as BitsetBase>::from_index(1) == Some(Page::Contents));
+    check!(<Page as BitsetBase>::from_index(3) == None);
+}
@@ -0,0 +1,118 @@
+use std::net::Shutdown;
+
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+mod proto {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Frame {
+        Ping,
+        Data(u32),
+        Close,
+        Reset,
+    }
+}
+
+enum_bitset::remote!(
+    #[bitset(serde = false)]
+    pub ShutdownSet for Shutdown { Read, Write, Both }
+);
+
+enum_bitset::remote!(
+    #[bitset(serde = false, repr = u16)]
+    FrameSet for self::proto::Frame {
+        Ping,
+        #[bitset(skip)]
+        Data,
+        #[bitset(bit = 8)]
+        Close,
+        // Not part of `Frame`, and compiled out in tests
+        #[cfg(not(test))]
+        Legacy,
+        Reset,
+    }
+);
+
+mod gated {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Remote {
+        A,
+        E,
+    }
+
+    #[derive(enum_bitset::EnumBitset, Debug, Clone, Copy, PartialEq)]
+    #[bitset(serde = false)]
+    pub enum Local {
+        A,
+        #[cfg(any())]
+        D,
+        E,
+    }
+
+    enum_bitset::remote!(
+        #[bitset(serde = false)]
+        pub RemoteSet for Remote { A, #[cfg(any())] D, E }
+    );
+}
+
+mod nested {
+    enum_bitset::remote!(
+        #[bitset(serde = false)]
+        pub OrderingSet for ::core::cmp::Ordering { Less, Equal, Greater }
+    );
+
+    enum_bitset::remote!(
+        #[bitset(serde = false)]
+        pub(crate) FrameKinds for super::proto::Frame { Ping, #[bitset(skip)] Data, Close, Reset }
+    );
+}
+
+
+#[test]
+fn same_api() {
+    let mut set = ShutdownSet::empty();
+    set.insert(Shutdown::Read);
+    set = set | Shutdown::Write;
+
+    check!(set.len() == 2);
+    check!(set.contains(Shutdown::Read));
+    check!(!set.contains(Shutdown::Both));
+    check!(set.iter().collect::<Vec<_>>() == [Shutdown::Read, Shutdown::Write]);
+    check!(ShutdownSet::all().to_repr() == 0b111);
+    check!(ShutdownSet::from_array([Shutdown::Both]) == Shutdown::Both.into());
+    check!(<ShutdownSet as EnumBitset>::MASK == 0b111);
+    check!(format!("{set:?}") == "ShutdownSet(2){Read, Write}");
+}
+
+#[test]
+fn configured_variants() {
+    use proto::Frame;
+
+    check!(FrameSet::VARIANTS == [Frame::Ping, Frame::Close, Frame::Reset]);
+    check!(FrameSet::from_array([Frame::Close]).to_repr() == 1 << 8);
+    check!(FrameSet::from_array([Frame::Reset]).to_repr() == 1 << 9);
+
+    let mut set = FrameSet::empty();
+    check!(!set.try_insert(Frame::Data(3)));
+    check!(set.is_empty());
+}
+
+#[test]
+fn paths() {
+    use core::cmp::Ordering;
+
+    let set: nested::OrderingSet = [Ordering::Less, Ordering::Greater].into_iter().collect();
+    check!(set.len() == 2);
+    check!(nested::FrameKinds::all().contains(proto::Frame::Reset));
+}
+
+#[test]
+fn gated_variants_match_the_derive() {
+    use gated::{Local, LocalSet, Remote, RemoteSet};
+
+    check!(LocalSet::MASK == 0b11);
+    check!(RemoteSet::MASK == LocalSet::MASK);
+    check!(Local::E.as_bitset().to_repr() == 0b10);
+    check!(RemoteSet::from_array([Remote::E]).to_repr() == 0b10);
+    check!(RemoteSet::VARIANTS == [Remote::A, Remote::E]);
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, to_string};
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, rename_all = "lowercase")]
+enum Role {
+    Reader,
+    Editor,
+    #[bitset(bit = 4)]
+    Admin,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false, repr = [u64; 2])]
+enum Wide {
+    First,
+    #[bitset(bit = 100)]
+    Last,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = false)]
+enum Dimension {
+    Width,
+    #[bitset(rename = "depth,height")]
+    Depth,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Record {
+    #[serde(with = "enum_bitset::serde::as_repr")]
+    repr: RoleSet,
+    #[serde(with = "enum_bitset::serde::as_list")]
+    list: RoleSet,
+    #[serde(with = "enum_bitset::serde::as_map")]
+    map: RoleSet,
+    #[serde(with = "enum_bitset::serde::as_string")]
+    string: RoleSet,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Dimensions {
+    #[serde(with = "enum_bitset::serde::as_string")]
+    set: DimensionSet,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct WideRecord {
+    #[serde(with = "enum_bitset::serde::as_repr")]
+    set: WideSet,
+}
+
+#[test]
+fn roundtrip() {
+    let roles = Role::Reader | Role::Admin;
+    let record = Record {
+        repr: roles,
+        list: roles,
+        map: roles,
+        string: roles,
+    };
+
+    let json = to_string(&record).unwrap();
+    check!(
+        json == r#"{"repr":17,"list":["Reader","Admin"],"map":{"reader":true,"editor":false,"admin":true},"string":"reader,admin"}"#
+    );
+    check!(from_str::<Record>(&json).unwrap() == record);
+
+    let wide = WideRecord {
+        set: WideSet::all(),
+    };
+    let json = to_string(&wide).unwrap();
+    check!(json == r#"{"set":[1,68719476736]}"#);
+    check!(from_str::<WideRecord>(&json).unwrap() == wide);
+}
+
+#[test]
+fn lenient_input() {
+    let record: Record = from_str(
+        r#"{"repr":0,"list":["Editor","Editor"],"map":{"editor":true},"string":" editor , admin "}"#,
+    )
+    .unwrap();
+
+    check!(record.repr == RoleSet::empty());
+    check!(record.list == Role::Editor.as_bitset());
+    check!(record.map == Role::Editor.as_bitset());
+    check!(record.string == Role::Editor | Role::Admin);
+
+    let record: Record = from_str(r#"{"repr":0,"list":[],"map":{},"string":""}"#).unwrap();
+    check!(record.string == RoleSet::empty());
+}
+
+#[test]
+fn errors() {
+    let_assert!(Err(error) = from_str::<Record>(r#"{"repr":8,"list":[],"map":{},"string":""}"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("invalid value: reserved bits set, expected a valid set representation")
+    );
+
+    let_assert!(
+        Err(error) = from_str::<Record>(r#"{"repr":0,"list":[],"map":{"owner":true},"string":""}"#)
+    );
+    check!(
+        error
+            .to_string()
+            .starts_with("unknown variant `owner`, expected one of `reader`, `editor`, `admin`")
+    );
+
+    let_assert!(
+        Err(error) =
+            from_str::<Record>(r#"{"repr":0,"list":[],"map":{},"string":"reader,Editor"}"#)
+    );
+    check!(
+        error
+            .to_string()
+            .starts_with("unknown variant `Editor`")
+    );
+}
+
+#[cfg(feature = "serde_with")]
+mod serde_with_adapters {
+    use enum_bitset::serde::{AsList, AsMap, AsRepr, AsString};
+    use serde_with::serde_as;
+
+    use super::*;
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Nested {
+        #[serde_as(as = "Option<AsRepr>")]
+        repr: Option<RoleSet>,
+        #[serde_as(as = "Vec<AsString>")]
+        strings: Vec<RoleSet>,
+        #[serde_as(as = "[AsList; 2]")]
+        lists: [RoleSet; 2],
+        #[serde_as(as = "Option<AsMap>")]
+        map: Option<RoleSet>,
+    }
+
+    #[test]
+    fn nested() {
+        let value = Nested {
+            repr: Some(Role::Editor.as_bitset()),
+            strings: vec![RoleSet::empty(), RoleSet::all()],
+            lists: [Role::Admin.as_bitset(), RoleSet::empty()],
+            map: None,
+        };
+
+        let json = to_string(&value).unwrap();
+        check!(
+            json == r#"{"repr":2,"strings":["","reader,editor,admin"],"lists":[["Admin"],[]],"map":null}"#
+        );
+        check!(from_str::<Nested>(&json).unwrap() == value);
+    }
+}
+
+#[test]
+fn separator_in_name() {
+    let dimensions = Dimensions { set: Dimension::Width.as_bitset() };
+
+    let_assert!(Err(error) = to_string(&dimensions));
+    check!(error.to_string() == r#"the variant name `depth,height` contains the separator ",""#);
+
+    let_assert!(Err(error) = from_str::<Dimensions>(r#"{"set":"Width"}"#));
+    check!(error.to_string().starts_with(r#"the variant name `depth,height` contains the separator ",""#));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde::{Deserialize, Serialize};
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "adaptive")]
+enum Permission {
+    Read,
+    Write,
+    #[bitset(bit = 4)]
+    Exec,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "adaptive", repr = [u64; 2])]
+enum Wide {
+    First,
+    #[bitset(bit = 100)]
+    Last,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "adaptive")]
+enum Marked<T> {
+    A,
+    B,
+    #[bitset(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+#[test]
+fn human_readable() {
+    let set = Permission::Read | Permission::Exec;
+    check!(serde_json::to_string(&set).unwrap() == r#"["Read","Exec"]"#);
+    check!(serde_json::from_str::<PermissionSet>(r#"["Exec","Read"]"#).unwrap() == set);
+    check!(serde_json::to_string(&Wide::Last.as_bitset()).unwrap() == r#"["Last"]"#);
+    check!(serde_json::to_string(&MarkedSet::<u8>::all()).unwrap() == r#"["A","B"]"#);
+
+    let_assert!(Err(_) = serde_json::from_str::<PermissionSet>("17"));
+}
+
+#[test]
+fn compact() {
+    let set = Permission::Read | Permission::Exec;
+    check!(bincode::serialize(&set).unwrap() == [17]);
+    check!(bincode::deserialize::<PermissionSet>(&[17]).unwrap() == set);
+
+    let wide = WideSet::all();
+    let bytes = bincode::serialize(&wide).unwrap();
+    check!(bytes.len() == 16);
+    check!(bincode::deserialize::<WideSet>(&bytes).unwrap() == wide);
+
+    let marked = MarkedSet::<u8>::from_array([Marked::B]);
+    check!(bincode::serialize(&marked).unwrap() == [2]);
+    check!(bincode::deserialize::<MarkedSet<u8>>(&[2]).unwrap() == marked);
+}
+
+#[test]
+fn compact_reserved_bits() {
+    let_assert!(Err(error) = bincode::deserialize::<PermissionSet>(&[8]));
+    check!(error.to_string().contains("reserved bits set"));
+}
@@ -59,3 +59,71 @@ fn assert_no_serialize() {
     check!(Wrapper::<SerdeConfigTest>::IMPLS == true);
     check!(Wrapper::<SerdeConfigTestSet>::IMPLS == false);
 }
+
+
+/// Whether `$ty` implements `$trait`, with the same trick as `assert_no_serialize`.
+macro_rules! impls {
+    ($ty:ty: $trait:path) => {{
+        #[allow(dead_code)]
+        trait DoesNotImpl {
+            const IMPLS: bool = false;
+        }
+
+        impl<T: ?Sized> DoesNotImpl for T {}
+
+        struct Wrapper<T: ?Sized>(std::marker::PhantomData<T>);
+
+        #[allow(dead_code)]
+        impl<T: ?Sized + $trait> Wrapper<T> {
+            const IMPLS: bool = true;
+        }
+
+        Wrapper::<$ty>::IMPLS
+    }};
+}
+
+#[derive(EnumBitset, PartialEq, Clone, Debug)]
+#[bitset(serde = "repr")]
+#[bitset(serde = false)]
+enum ReprOff {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, PartialEq, Clone, Debug)]
+#[bitset(serde = "map", serde = "ser")]
+enum MapSerOnly {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, PartialEq, Clone, Debug)]
+#[bitset(serde = "string", serde = "de")]
+enum StringDeOnly {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[bitset(serde = "adaptive", serde = "none")]
+enum AdaptiveOff {
+    A,
+    B,
+}
+
+#[test]
+fn formats_follow_ser_and_de() {
+    check!(!impls!(ReprOffSet: Serialize));
+    check!(!impls!(ReprOffSet: Deserialize<'static>));
+
+    check!(impls!(MapSerOnlySet: Serialize));
+    check!(!impls!(MapSerOnlySet: Deserialize<'static>));
+    check!(serde_json::to_string(&MapSerOnlySet::from([MapSerOnly::B])).unwrap() == r#"{"A":false,"B":true}"#);
+
+    check!(!impls!(StringDeOnlySet: Serialize));
+    check!(impls!(StringDeOnlySet: Deserialize<'static>));
+    check!(from_str::<StringDeOnlySet>(r#""A,B""#).unwrap() == StringDeOnlySet::all());
+
+    check!(!impls!(AdaptiveOffSet: Serialize));
+    check!(!impls!(AdaptiveOffSet: Deserialize<'static>));
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde_json::{from_str, to_string};
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "map", rename_all = "snake_case")]
+enum Feature {
+    DarkMode,
+    #[bitset(rename = "beta")]
+    BetaChannel,
+    #[bitset(bit = 5)]
+    Telemetry,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "map", serde_unknown = "ignore")]
+enum Lenient {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "map")]
+enum Marked<T> {
+    A,
+    B,
+    #[bitset(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+struct NotSerde;
+
+#[test]
+fn serialize() {
+    check!(
+        to_string(&FeatureSet::empty()).unwrap()
+            == r#"{"dark_mode":false,"beta":false,"telemetry":false}"#
+    );
+    check!(
+        to_string(&(Feature::DarkMode | Feature::Telemetry)).unwrap()
+            == r#"{"dark_mode":true,"beta":false,"telemetry":true}"#
+    );
+    check!(to_string(&MarkedSet::<NotSerde>::all()).unwrap() == r#"{"A":true,"B":true}"#);
+}
+
+#[test]
+fn deserialize() {
+    check!(
+        from_str::<FeatureSet>(r#"{"dark_mode":true,"beta":false,"telemetry":true}"#).unwrap()
+            == Feature::DarkMode | Feature::Telemetry
+    );
+    check!(from_str::<FeatureSet>(r#"{"beta":true}"#).unwrap() == Feature::BetaChannel.as_bitset());
+    check!(from_str::<FeatureSet>("{}").unwrap() == FeatureSet::empty());
+    check!(
+        from_str::<MarkedSet<NotSerde>>(r#"{"B":true}"#).unwrap()
+            == MarkedSet::from_array([Marked::B])
+    );
+
+    for set in [
+        FeatureSet::empty(),
+        FeatureSet::all(),
+        Feature::BetaChannel.as_bitset(),
+    ] {
+        check!(from_str::<FeatureSet>(&to_string(&set).unwrap()).unwrap() == set);
+    }
+}
+
+#[test]
+fn unknown_keys() {
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#"{"dark_mode":true,"DarkMode":true}"#));
+    check!(error.to_string().starts_with(
+        "unknown variant `DarkMode`, expected one of `dark_mode`, `beta`, `telemetry`"
+    ));
+
+    check!(
+        from_str::<LenientSet>(r#"{"A":true,"C":{"nested":[1,2]},"B":false}"#).unwrap()
+            == Lenient::A.as_bitset()
+    );
+}
+
+#[test]
+fn invalid_values() {
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#"{"beta":1}"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("invalid type: integer `1`, expected a boolean")
+    );
+
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#"["beta"]"#));
+    check!(error.to_string().starts_with(
+        "invalid type: sequence, expected a map from the variants of FeatureSet to booleans"
+    ));
+}
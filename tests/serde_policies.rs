@@ -0,0 +1,129 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+enum Plain {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde_duplicates = "error")]
+#[serde(rename_all = "lowercase")]
+enum Strict {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde_unknown = "ignore")]
+enum Lenient {
+    A,
+    #[serde(alias = "Bee")]
+    B,
+}
+
+#[derive(EnumBitset, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde_unknown = "ignore", serde_duplicates = "error")]
+enum Marked<T> {
+    A,
+    B,
+    #[bitset(skip)]
+    #[serde(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "string", serde_duplicates = "error")]
+enum StrictString {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "map", serde_duplicates = "error")]
+enum StrictMap {
+    A,
+    B,
+}
+
+#[test]
+fn defaults() {
+    check!(from_str::<PlainSet>(r#"["A","A"]"#).unwrap() == Plain::A.as_bitset());
+
+    let_assert!(Err(error) = from_str::<PlainSet>(r#"["A","C"]"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("unknown variant `C`")
+    );
+}
+
+#[test]
+fn duplicates() {
+    check!(from_str::<StrictSet>(r#"["b","a"]"#).unwrap() == StrictSet::all());
+
+    let_assert!(Err(error) = from_str::<StrictSet>(r#"["a","b","a"]"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("duplicate variant `A` in StrictSet")
+    );
+
+    let_assert!(Err(error) = from_str::<MarkedSet<u8>>(r#"["B","B"]"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("duplicate variant `B` in MarkedSet")
+    );
+
+    let_assert!(Err(error) = from_str::<StrictStringSet>(r#""A,B,A""#));
+    check!(
+        error
+            .to_string()
+            .starts_with("duplicate variant `A` in StrictStringSet")
+    );
+
+    let_assert!(Err(error) = from_str::<StrictMapSet>(r#"{"B":false,"A":true,"B":true}"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("duplicate variant `B` in StrictMapSet")
+    );
+}
+
+#[test]
+fn unknown() {
+    check!(from_str::<LenientSet>(r#"["C","A","D"]"#).unwrap() == Lenient::A.as_bitset());
+    check!(from_str::<LenientSet>(r#"["Bee","A"]"#).unwrap() == LenientSet::all());
+    check!(from_str::<LenientSet>(r#"["A","A"]"#).unwrap() == Lenient::A.as_bitset());
+    check!(
+        from_str::<MarkedSet<u8>>(r#"["New","B"]"#).unwrap() == MarkedSet::from_array([Marked::B])
+    );
+
+    // Only unknown names are skipped
+    let_assert!(Err(error) = from_str::<LenientSet>(r#"["A",1]"#));
+    check!(
+        error
+            .to_string()
+            .starts_with("invalid type: integer `1`, expected the name of a variant")
+    );
+}
+
+#[test]
+fn unknown_from_reader() {
+    // Escaped strings are not borrowed from the input
+    let set: LenientSet = serde_json::from_reader(r#"["A","New"]"#.as_bytes()).unwrap();
+    check!(set == Lenient::A.as_bitset());
+}
+
+#[test]
+fn unknown_compact() {
+    let set = Lenient::A | Lenient::B;
+    let bytes = bincode::serialize(&set).unwrap();
+    check!(bincode::deserialize::<LenientSet>(&bytes).unwrap() == set);
+}
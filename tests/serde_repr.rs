@@ -0,0 +1,72 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde_json::{from_str, to_string};
+
+// Does not implement the serde traits
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "repr")]
+enum Permission {
+    Read,
+    Write,
+    #[bitset(bit = 4)]
+    Exec,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "repr", repr = [u64; 2])]
+enum Wide {
+    First,
+    #[bitset(bit = 100)]
+    Last,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "repr")]
+enum Marked<T> {
+    A,
+    B,
+    #[bitset(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+struct NotSerde;
+
+#[test]
+fn serialize() {
+    check!(to_string(&PermissionSet::empty()).unwrap() == "0");
+    check!(to_string(&(Permission::Read | Permission::Exec)).unwrap() == "17");
+    check!(to_string(&WideSet::all()).unwrap() == "[1,68719476736]");
+    check!(to_string(&MarkedSet::<NotSerde>::all()).unwrap() == "3");
+}
+
+#[test]
+fn deserialize() {
+    check!(from_str::<PermissionSet>("3").unwrap() == Permission::Read | Permission::Write);
+    check!(from_str::<WideSet>("[0,68719476736]").unwrap() == Wide::Last.as_bitset());
+    check!(from_str::<MarkedSet<NotSerde>>("2").unwrap() == MarkedSet::from_array([Marked::B]));
+
+    for set in [
+        PermissionSet::empty(),
+        PermissionSet::all(),
+        Permission::Exec.as_bitset(),
+    ] {
+        check!(from_str::<PermissionSet>(&to_string(&set).unwrap()).unwrap() == set);
+    }
+}
+
+#[test]
+fn reserved_bits() {
+    let_assert!(Err(error) = from_str::<PermissionSet>("4"));
+    check!(
+        error
+            .to_string()
+            .contains("reserved bits set, expected a valid PermissionSet representation")
+    );
+
+    check!(from_str::<PermissionSet>("256").is_err());
+    check!(from_str::<WideSet>("[2,0]").is_err());
+    check!(from_str::<WideSet>("[1]").is_err());
+    check!(from_str::<PermissionSet>("[\"Read\"]").is_err());
+}
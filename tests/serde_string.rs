@@ -0,0 +1,100 @@
+#![cfg(feature = "serde")]
+
+use assert2::{check, let_assert};
+use enum_bitset::EnumBitset;
+use serde::Deserialize;
+use serde_json::{from_str, to_string};
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "string", rename_all = "lowercase")]
+enum Feature {
+    Metrics,
+    Tracing,
+    #[bitset(bit = 7)]
+    Profiling,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "string", sep = " | ", serde_unknown = "ignore")]
+enum Lenient {
+    A,
+    B,
+}
+
+#[derive(EnumBitset, Debug, Clone, Copy, PartialEq)]
+#[bitset(serde = "string")]
+enum Marked<T> {
+    A,
+    B,
+    #[bitset(skip)]
+    _Marker(std::marker::PhantomData<T>),
+}
+
+struct NotSerde;
+
+#[derive(Deserialize)]
+struct Config {
+    features: FeatureSet,
+}
+
+#[test]
+fn serialize() {
+    check!(to_string(&FeatureSet::empty()).unwrap() == r#""""#);
+    check!(
+        to_string(&(Feature::Profiling | Feature::Metrics)).unwrap() == r#""metrics,profiling""#
+    );
+    check!(to_string(&LenientSet::all()).unwrap() == r#""A | B""#);
+    check!(to_string(&MarkedSet::<NotSerde>::all()).unwrap() == r#""A,B""#);
+}
+
+#[test]
+fn deserialize() {
+    check!(
+        from_str::<FeatureSet>(r#""metrics,tracing""#).unwrap()
+            == Feature::Metrics | Feature::Tracing
+    );
+    check!(
+        from_str::<FeatureSet>(r#"" tracing ,  profiling ""#).unwrap()
+            == Feature::Tracing | Feature::Profiling
+    );
+    check!(from_str::<FeatureSet>(r#""""#).unwrap() == FeatureSet::empty());
+    check!(from_str::<FeatureSet>(r#""   ""#).unwrap() == FeatureSet::empty());
+    check!(from_str::<LenientSet>(r#""B|A""#).unwrap() == LenientSet::all());
+    check!(
+        from_str::<MarkedSet<NotSerde>>(r#""B""#).unwrap() == MarkedSet::from_array([Marked::B])
+    );
+
+    let config: Config = from_str(r#"{"features": "tracing"}"#).unwrap();
+    check!(config.features == Feature::Tracing.as_bitset());
+
+    for set in [
+        FeatureSet::empty(),
+        FeatureSet::all(),
+        Feature::Profiling.as_bitset(),
+    ] {
+        check!(from_str::<FeatureSet>(&to_string(&set).unwrap()).unwrap() == set);
+    }
+}
+
+#[test]
+fn unknown_names() {
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#""metrics,Tracing""#));
+    check!(error.to_string().starts_with(
+        "unknown variant `Tracing`, expected one of `metrics`, `tracing`, `profiling`"
+    ));
+
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#""metrics,,tracing""#));
+    check!(
+        error
+            .to_string()
+            .starts_with("unknown variant ``")
+    );
+
+    check!(from_str::<LenientSet>(r#""A | C | D""#).unwrap() == Lenient::A.as_bitset());
+}
+
+#[test]
+fn invalid_type() {
+    let_assert!(Err(error) = from_str::<FeatureSet>(r#"["metrics"]"#));
+    check!(error.to_string().starts_with(r#"invalid type: sequence, expected a string with the variants of FeatureSet, separated by ",""#));
+}
@@ -0,0 +1,84 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Permission {
+    Read,
+    Write,
+    Exec,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, repr = u32)]
+enum Mode {
+    Idle,
+    Busy,
+}
+
+
+fn missing<S: EnumBitset>(set: &S) -> Vec<S::Base> {
+    S::VARIANTS
+        .iter()
+        .filter(|variant| !set.contains(*variant))
+        .cloned()
+        .collect()
+}
+
+fn toggle<S: EnumBitset>(set: &mut S, item: S::Base) {
+    if set.contains(&item) {
+        set.remove(item);
+    } else {
+        set.insert(item);
+    }
+}
+
+
+#[test]
+fn associated_items() {
+    check!(<PermissionSet as EnumBitset>::MASK == 0b111);
+    check!(<PermissionSet as EnumBitset>::VARIANTS == [Permission::Read, Permission::Write, Permission::Exec]);
+    check!(<ModeSet as EnumBitset>::MASK == 0b11u32);
+    check!(<ModeSet as EnumBitset>::VARIANTS == [Mode::Idle, Mode::Busy]);
+}
+
+#[test]
+fn generic_missing() {
+    check!(missing(&PermissionSet::from([Permission::Write])) == vec![Permission::Read, Permission::Exec]);
+    check!(missing(&ModeSet::all()).is_empty());
+    check!(missing(&ModeSet::empty()) == vec![Mode::Idle, Mode::Busy]);
+}
+
+#[test]
+fn generic_toggle() {
+    let mut set = PermissionSet::empty();
+
+    toggle(&mut set, Permission::Exec);
+    check!(set == PermissionSet::from([Permission::Exec]));
+
+    toggle(&mut set, Permission::Read);
+    toggle(&mut set, Permission::Exec);
+    check!(set == PermissionSet::from([Permission::Read]));
+}
+
+#[test]
+fn generic_constructors() {
+    fn roundtrip<S: EnumBitset>(set: S) -> Option<S> {
+        S::from_repr(set.to_repr())
+    }
+
+    check!(<PermissionSet as EnumBitset>::empty().is_empty());
+    check!(<PermissionSet as EnumBitset>::all().len() == 3);
+    check!(EnumBitset::union(&ModeSet::from([Mode::Idle]), &Mode::Busy.into()) == ModeSet::all());
+    check!(roundtrip(PermissionSet::from([Permission::Read, Permission::Exec])) == Some(PermissionSet::from([Permission::Read, Permission::Exec])));
+    check!(<ModeSet as EnumBitset>::from_repr(0b100) == None);
+}
+
+#[test]
+fn generic_iter() {
+    fn collect<S: EnumBitset>(set: &S) -> Vec<S::Base> {
+        EnumBitset::iter(set).collect()
+    }
+
+    check!(collect(&PermissionSet::from([Permission::Exec, Permission::Read])) == vec![Permission::Read, Permission::Exec]);
+}
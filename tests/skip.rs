@@ -0,0 +1,92 @@
+use assert2::check;
+use enum_bitset::{BitsetBase, EnumBitset};
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum State {
+    Idle,
+    #[bitset(skip)]
+    Unknown,
+    Running,
+    #[bitset(skip)]
+    Invalid(u8),
+    Done,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, bits = "discriminant")]
+#[repr(u8)]
+enum Code {
+    Ok = 0,
+    #[bitset(skip)]
+    Reserved = 3,
+    // Follows the discriminant of `Reserved`
+    Retry,
+}
+
+
+#[test]
+fn skipped_variants_have_no_bit() {
+    check!(StateSet::MASK == 0b111);
+    check!(State::Running.as_bitset().to_repr() == 0b10);
+    check!(State::Done.as_bitset().to_repr() == 0b100);
+    check!(State::Unknown.as_bitset().is_empty());
+    check!(StateSet::from([State::Invalid(3)]).is_empty());
+
+    check!(CodeSet::MASK == 0b1_0001);
+    check!(Code::Reserved.as_bitset().is_empty());
+    check!(Code::Retry.as_bitset().to_repr() == 1 << (Code::Retry as u8));
+}
+
+#[test]
+fn skipped_variants_are_not_listed() {
+    check!(StateSet::VARIANTS == [State::Idle, State::Running, State::Done]);
+    check!(StateSet::all().len() == 3);
+    check!(State::COUNT == 3);
+    check!(State::Done.index() == 2);
+
+    let items: Vec<_> = StateSet::all().iter().collect();
+    check!(items == [State::Idle, State::Running, State::Done]);
+}
+
+#[test]
+fn insert_skipped() {
+    let mut set = StateSet::from([State::Idle]);
+
+    set.insert(State::Unknown);
+    check!(set == StateSet::from([State::Idle]));
+    check!(!set.contains(State::Unknown));
+
+    check!(!set.try_insert(State::Invalid(0)));
+    check!(set == StateSet::from([State::Idle]));
+
+    check!(set.try_insert(State::Done));
+    check!(set == State::Idle | State::Done);
+
+    let collected: StateSet = [State::Unknown, State::Running].into_iter().collect();
+    check!(collected == StateSet::from([State::Running]));
+}
+
+#[test]
+#[should_panic(expected = "`State::Unknown` is skipped")]
+fn index_of_skipped() {
+    State::Unknown.index();
+}
+
+#[derive(EnumBitset, Clone, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Message {
+    Ping,
+    Pong,
+    #[bitset(skip)]
+    Text(String),
+}
+
+#[test]
+fn skipped_variant_with_drop() {
+    const PING: MessageSet = MessageSet::from_array([Message::Ping]);
+    check!(PING.to_repr() == 0b1);
+
+    let set = MessageSet::from([Message::Text(String::from("hi")), Message::Pong]);
+    check!(set == MessageSet::from([Message::Pong]));
+}
@@ -0,0 +1,156 @@
+use assert2::check;
+use enum_bitset::EnumBitset;
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Feature {
+    Cache,
+    Compression,
+    Tls,
+    Metrics,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false)]
+enum Wide {
+    A,
+    #[bitset(bit = 63)]
+    B,
+    #[bitset(bit = 64)]
+    C,
+    #[bitset(bit = 130)]
+    D,
+}
+
+#[derive(EnumBitset, Clone, Copy, Debug, PartialEq)]
+#[bitset(serde = false, repr = u8)]
+enum Byte {
+    B0,
+    B1,
+    B2,
+    B3,
+    B4,
+    B5,
+    B6,
+    B7,
+}
+
+#[test]
+fn subsets() {
+    let set = Feature::Cache | Feature::Tls | Feature::Metrics;
+    let subsets: Vec<FeatureSet> = set.subsets().collect();
+
+    check!(
+        subsets
+            == [
+                FeatureSet::empty(),
+                Feature::Cache.as_bitset(),
+                Feature::Tls.as_bitset(),
+                Feature::Cache | Feature::Tls,
+                Feature::Metrics.as_bitset(),
+                Feature::Cache | Feature::Metrics,
+                Feature::Tls | Feature::Metrics,
+                set,
+            ]
+    );
+
+    check!(FeatureSet::empty().subsets().collect::<Vec<_>>() == [FeatureSet::empty()]);
+    check!(FeatureSet::all().subsets().count() == 16);
+    check!(ByteSet::all().subsets().count() == 256);
+    check!(ByteSet::all().subsets().last() == Some(ByteSet::all()));
+}
+
+#[test]
+fn subsets_across_words() {
+    let subsets: Vec<WideSet> = WideSet::all().subsets().collect();
+
+    check!(subsets.len() == 16);
+    check!(subsets[0] == WideSet::empty());
+    check!(subsets[1] == Wide::A.as_bitset());
+    check!(subsets[2] == Wide::B.as_bitset());
+    check!(subsets[4] == Wide::C.as_bitset());
+    check!(subsets[8] == Wide::D.as_bitset());
+    check!(subsets[15] == WideSet::all());
+}
+
+#[test]
+fn supersets() {
+    let required = Feature::Tls | Feature::Metrics;
+    let supersets: Vec<FeatureSet> = required.supersets().collect();
+
+    check!(
+        supersets
+            == [
+                required,
+                required | Feature::Cache,
+                required | Feature::Compression,
+                FeatureSet::all(),
+            ]
+    );
+
+    check!(FeatureSet::all().supersets().collect::<Vec<_>>() == [FeatureSet::all()]);
+    check!(FeatureSet::empty().supersets().count() == 16);
+    check!(Wide::D.as_bitset().supersets().count() == 8);
+}
+
+#[test]
+fn combinations() {
+    let set = FeatureSet::all();
+    let pairs: Vec<FeatureSet> = set.combinations(2).collect();
+
+    check!(
+        pairs
+            == [
+                Feature::Cache | Feature::Compression,
+                Feature::Cache | Feature::Tls,
+                Feature::Compression | Feature::Tls,
+                Feature::Cache | Feature::Metrics,
+                Feature::Compression | Feature::Metrics,
+                Feature::Tls | Feature::Metrics,
+            ]
+    );
+
+    check!(set.combinations(0).collect::<Vec<_>>() == [FeatureSet::empty()]);
+    check!(set.combinations(4).collect::<Vec<_>>() == [set]);
+    check!(set.combinations(5).next() == None);
+    check!(set.combinations(usize::MAX).next() == None);
+
+    let partial = Feature::Cache | Feature::Tls | Feature::Metrics;
+    check!(
+        partial.combinations(2).collect::<Vec<_>>()
+            == [
+                Feature::Cache | Feature::Tls,
+                Feature::Cache | Feature::Metrics,
+                Feature::Tls | Feature::Metrics,
+            ]
+    );
+}
+
+#[test]
+fn combinations_fill_the_repr() {
+    check!(ByteSet::all().combinations(3).count() == 56);
+    check!(ByteSet::all().combinations(8).count() == 1);
+    check!(
+        ByteSet::all()
+            .combinations(3)
+            .all(|set| set.len() == 3)
+    );
+
+    check!(WideSet::all().combinations(2).count() == 6);
+    check!(WideSet::all().combinations(3).last() == Some(Wide::B | Wide::C | Wide::D));
+}
+
+#[test]
+fn agrees_with_subsets() {
+    let set = FeatureSet::all();
+
+    for k in 0..=4 {
+        let expected: Vec<FeatureSet> = set
+            .subsets()
+            .filter(|subset| subset.len() == k)
+            .collect();
+        let mut combinations: Vec<FeatureSet> = set.combinations(k).collect();
+        combinations.sort();
+        check!(combinations == expected, "k = {k}");
+    }
+}